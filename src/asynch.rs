@@ -0,0 +1,115 @@
+//! Driver for `embedded-hal-async` SPI devices.
+//!
+//! [`LTC2983Async`] mirrors the basic operations of [`crate::LTC2983`]. While waiting for a
+//! conversion it yields to other tasks between the status reads instead of busy polling.
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::spi::SpiDevice;
+
+use crate::{decode_result, validate_references, GlobalConfiguration, LTC2983Channel, LTC2983Error, LTC2983Result, LTC2983Status,
+            ThermalProbeType, LTC2983_READ, LTC2983_WRITE, STATUS_REGISTER, GLOBAL_CONFIG_REGISTER};
+
+pub struct LTC2983Async<SPI, D> {
+    spi_device: SPI,
+    delay: D,
+    interval_us: u32,
+    timeout_us: u32,
+    channels: [Option<ThermalProbeType>; 20],
+    global_configuration: GlobalConfiguration
+}
+
+impl<SPI, D> LTC2983Async<SPI, D>
+where
+    SPI: SpiDevice,
+    D: DelayNs
+{
+    ///the status is polled every millisecond while waiting for a conversion, for at most one second
+    pub fn new(spi_device: SPI, delay: D) -> Self {
+        Self::with_poll_interval(spi_device, delay, 1_000, 1_000_000)
+    }
+
+    ///poll the status every `interval_us` while waiting for a conversion, giving up after `timeout_us`
+    ///
+    ///A zero interval polls without sleeping, every poll then counts as 1µs towards the timeout.
+    pub fn with_poll_interval(spi_device: SPI, delay: D, interval_us: u32, timeout_us: u32) -> Self {
+        LTC2983Async {
+            spi_device,
+            delay,
+            interval_us,
+            timeout_us,
+            channels: Default::default(),
+            global_configuration: GlobalConfiguration::default()
+        }
+    }
+
+    pub async fn status(&mut self) -> Result<LTC2983Status, LTC2983Error<SPI::Error>> {
+        let [addr_high, addr_low] = STATUS_REGISTER.to_be_bytes();
+        let read_status_bytes = [LTC2983_READ, addr_high, addr_low, 0x0]; //Dummy Data
+
+        let mut recv: [u8; 4] = [0, 0, 0, 0];
+        self.spi_device.transfer(&mut recv, &read_status_bytes).await?;
+        Ok(LTC2983Status::from(recv[3]))
+    }
+
+    ///write channel configuration, see [`crate::LTC2983::setup_channel`]
+    pub async fn setup_channel(&mut self, probe: ThermalProbeType, channel: LTC2983Channel) -> Result<(), LTC2983Error<SPI::Error>> {
+        let word = probe.to_config_word().map_err(LTC2983Error::InvalidConfig)?;
+        probe.check_channel(channel).map_err(LTC2983Error::InvalidConfig)?;
+        validate_references(&probe, |channel| self.channels[channel.index()].as_ref()).map_err(LTC2983Error::InvalidConfig)?;
+        if self.status().await?.started() {
+            return Err(LTC2983Error::DeviceBusy);
+        }
+        let [addr_high, addr_low] = channel.start_address().to_be_bytes();
+        let [b3, b2, b1, b0] = word.to_be_bytes();
+        self.spi_device.write(&[LTC2983_WRITE, addr_high, addr_low, b3, b2, b1, b0]).await?;
+        self.channels[channel.index()] = Some(probe.into_written());
+        Ok(())
+    }
+
+    ///write the global configuration register (temperature unit and rejection filter)
+    pub async fn set_global_configuration(&mut self, config: GlobalConfiguration) -> Result<(), LTC2983Error<SPI::Error>> {
+        let [addr_high, addr_low] = GLOBAL_CONFIG_REGISTER.to_be_bytes();
+        self.spi_device.write(&[LTC2983_WRITE, addr_high, addr_low, config.to_bits()]).await?;
+        self.global_configuration = config;
+        Ok(())
+    }
+
+    pub async fn start_conversion(&mut self, channel: LTC2983Channel) -> Result<(), LTC2983Error<SPI::Error>> {
+        //start measurement, the upper 3 bits (0b100) start the conversion and the lower 5 bits select the channel
+        let [addr_high, addr_low] = STATUS_REGISTER.to_be_bytes();
+        self.spi_device.write(&[LTC2983_WRITE, addr_high, addr_low, 0x80 | channel.identifier() as u8]).await?;
+        Ok(())
+    }
+
+    ///wait for the running conversion to finish, yielding between the status reads
+    pub async fn wait_for_conversion(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        let mut waited: u32 = 0;
+        while !self.status().await?.done() {
+            if waited >= self.timeout_us {
+                return Err(LTC2983Error::ConversionTimeout);
+            }
+            self.delay.delay_us(self.interval_us).await;
+            waited = waited.saturating_add(self.interval_us.max(1));
+        }
+        Ok(())
+    }
+
+    ///read the result of the last conversion of a channel, see [`crate::LTC2983::read_temperature`]
+    pub async fn read_temperature(&mut self, channel: LTC2983Channel) -> Result<LTC2983Result, LTC2983Error<SPI::Error>> {
+        let [addr_high, addr_low] = channel.result_address().to_be_bytes();
+        let read_temperature_bytes = [LTC2983_READ, addr_high, addr_low, 0, 0, 0, 0]; //Dummy bytes for reading
+
+        let mut recv: [u8; 7] = [0, 0, 0, 0, 0, 0, 0];
+        self.spi_device.transfer(&mut recv, &read_temperature_bytes).await?;
+
+        let bytes = [recv[3], recv[4], recv[5], recv[6]];
+        Ok(decode_result(bytes, self.channels[channel.index()].as_ref(), &self.global_configuration))
+    }
+
+    ///start a conversion on the channel, wait for it to finish and read out the result
+    pub async fn measure(&mut self, channel: LTC2983Channel) -> Result<LTC2983Result, LTC2983Error<SPI::Error>> {
+        self.start_conversion(channel).await?;
+        self.wait_for_conversion().await?;
+        self.read_temperature(channel).await
+    }
+}
@@ -0,0 +1,90 @@
+//! Use a plain [`SpiBus`] with a manually driven chip select pin.
+//!
+//! The LTC2983 ends a command when chip select goes high, so CS has to stay low for the complete
+//! frame of instruction, address and data. [`BusDevice`] asserts CS for the whole transaction and
+//! releases it afterwards, also when the transfer failed.
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiBus, SpiDevice};
+
+/// Error of a [`BusDevice`] transaction
+#[derive(Debug)]
+pub enum BusDeviceError<BUS, CS> {
+    Spi(BUS),
+    ChipSelect(CS),
+    ///the transaction contained a [`Operation::DelayNs`], the operations before it were transferred
+    DelayUnsupported
+}
+
+impl<BUS, CS> embedded_hal::spi::Error for BusDeviceError<BUS, CS>
+where
+    BUS: embedded_hal::spi::Error,
+    CS: core::fmt::Debug
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            BusDeviceError::Spi(err) => err.kind(),
+            BusDeviceError::ChipSelect(_) => ErrorKind::ChipSelectFault,
+            BusDeviceError::DelayUnsupported => ErrorKind::Other
+        }
+    }
+}
+
+/// [`SpiDevice`] made of an exclusively owned [`SpiBus`] and its chip select pin
+///
+/// Delay operations are not supported, the driver never issues them. A transaction containing one
+/// fails with [`BusDeviceError::DelayUnsupported`].
+pub struct BusDevice<BUS, CS> {
+    bus: BUS,
+    cs: CS
+}
+
+impl<BUS, CS> BusDevice<BUS, CS>
+where
+    BUS: SpiBus,
+    CS: OutputPin
+{
+    ///take ownership of the bus and the chip select pin, the pin is driven high (deselected)
+    pub fn new(bus: BUS, mut cs: CS) -> Result<Self, CS::Error> {
+        cs.set_high()?;
+        Ok(BusDevice { bus, cs })
+    }
+
+    ///give back the bus and the chip select pin
+    pub fn release(self) -> (BUS, CS) {
+        (self.bus, self.cs)
+    }
+}
+
+impl<BUS, CS> ErrorType for BusDevice<BUS, CS>
+where
+    BUS: SpiBus,
+    CS: OutputPin
+{
+    type Error = BusDeviceError<BUS::Error, CS::Error>;
+}
+
+impl<BUS, CS> SpiDevice for BusDevice<BUS, CS>
+where
+    BUS: SpiBus,
+    CS: OutputPin
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        self.cs.set_low().map_err(BusDeviceError::ChipSelect)?;
+
+        let result = operations.iter_mut().try_for_each(|op| match op {
+            Operation::Read(read) => self.bus.read(read).map_err(BusDeviceError::Spi),
+            Operation::Write(write) => self.bus.write(write).map_err(BusDeviceError::Spi),
+            Operation::Transfer(read, write) => self.bus.transfer(read, write).map_err(BusDeviceError::Spi),
+            Operation::TransferInPlace(buf) => self.bus.transfer_in_place(buf).map_err(BusDeviceError::Spi),
+            Operation::DelayNs(_) => Err(BusDeviceError::DelayUnsupported),
+        });
+        //the frame only ends once all bytes have left the bus, that is also true for a failed transaction
+        let flushed = self.bus.flush().map_err(BusDeviceError::Spi);
+
+        let deselect = self.cs.set_high();
+        result?;
+        flushed?;
+        deselect.map_err(BusDeviceError::ChipSelect)
+    }
+}
@@ -9,13 +9,21 @@
 //! - [x] Theromcouple J,K,E,N,R,S,T,B
 //! - [ ] Custom Thermocouple
 //! - [x] RTD
-//! - [ ] Thermistor
+//! - [x] Thermistor
 //! - [x] Sense Resistor
 //! - [x] Diode
-//! - [ ] Direct ADC
+//! - [x] Direct ADC
+//!
+//!# Features
+//! - `defmt`: derive `defmt::Format` for the results, fault flags, status and errors
+//! - `async`: [`LTC2983Async`], a driver for `embedded-hal-async` SPI devices
+//! - `log`: log new faults of a channel and retried SPI transfers with the `log` crate
 //!
 //!# Example
-//!``` rust
+//!``` rust,no_run
+//!# use ltc2983::{LTC2983, ThermocoupleParameters};
+//!# use std::{thread::sleep, time::Duration};
+//!# fn run(device: impl embedded_hal::spi::SpiDevice) {
 //!    let mut ltc = LTC2983::new(device);
 //!
 //!    let _ = ltc.setup_channel(ltc2983::ThermalProbeType::Diode(ltc2983::DiodeParameters::default().ideality_factor(1.).excitation_current(ltc2983::DiodeExcitationCurrent::I20uA).num_reading(ltc2983::DiodeReadingCount::READ3)), ltc2983::LTC2983Channel::CH2);
@@ -31,37 +39,65 @@
 //!        println!("{result:#?}");
 //!        sleep(Duration::new(1, 0));
 //!    }
-//!
+//!# }
 //!```
 
-use std::{convert::TryInto,thread};
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::time::{Duration, Instant};
 
-use std::time::{Duration};
-use bytebuffer::ByteBuffer;
-use embedded_hal::spi::{SpiDevice, SpiBus};
-use fixed::{FixedU32, types::extra::{U10, U20}, FixedI32};
+use bytebuffer::{ByteBuffer, Endian};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::{SpiBus, SpiDevice};
+use fixed::{FixedU32, types::extra::{U6, U10, U20, U21}, FixedI32, FixedI64};
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
 
+pub mod bus;
+pub mod offline;
+#[cfg(feature = "async")]
+pub mod asynch;
+#[cfg(feature = "async")]
+pub use asynch::LTC2983Async;
+
 const LTC2983_WRITE: u8 = 0x2;
 const LTC2983_READ: u8 = 0x3;
 
 const STATUS_REGISTER: u16 = 0x000;
-//const GLOBAL_CONFIG_REGISTER: u16 = 0x0F0;
+const GLOBAL_CONFIG_REGISTER: u16 = 0x0F0;
 const MULTI_CHANNEL_MASK_REGISTER: u16 = 0x0F4;
+const MUX_CONFIG_DELAY_REGISTER: u16 = 0x0FF;
+const EEPROM_KEY_REGISTER: u16 = 0x0B0;
+const EEPROM_STATUS_REGISTER: u16 = 0x0F9;
+const LAST_REGISTER: u16 = 0x3CF;
+//custom sensor tables are stored from here up to the last register
+const CUSTOM_DATA_START: u16 = 0x250;
 
-#[derive(Debug)]
+//unlocks the EEPROM commands, written to 0x0B0-0x0B3
+const EEPROM_KEY: u32 = 0xA53C0F5A;
+const EEPROM_WRITE_COMMAND: u8 = 0x95;
+const EEPROM_READ_COMMAND: u8 = 0x96;
+
+/// Value used by [`LTC2983::read_all_packed`] for channels without a usable temperature
+pub const PACKED_FAULT: i16 = i16::MIN;
+
+/// How the negative input of a thermocouple or diode is connected
+///
+/// A single-ended sensor is measured between its channel and COM, so it can be placed on any
+/// channel including CH1. COM has to be connected to the negative lead (or the ground it shares), an
+/// offset between the two shows up in the result. A differential sensor is measured between its
+/// channel and the channel below.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum SensorConfiguration {
+    ///measured against COM
+    #[default]
     SingleEnded,
+    ///measured against the channel below
     Differential
 }
 
-impl Default for SensorConfiguration {
-    fn default() -> Self {
-        Self::SingleEnded
-    }
-}
-
 impl SensorConfiguration {
     pub fn identifier(&self) -> u64 {
         match self {
@@ -69,23 +105,79 @@ impl SensorConfiguration {
             SensorConfiguration::Differential => 0,
         }
     }
+
+    fn from_identifier(identifier: u64) -> Self {
+        match identifier {
+            0 => SensorConfiguration::Differential,
+            _ => SensorConfiguration::SingleEnded
+        }
+    }
 }
 
-#[derive(Debug)]
+//extract `width` bits starting at bit `shift` of a channel configuration word
+fn config_field(word: u32, shift: u32, width: u32) -> u64 {
+    ((word >> shift) & ((1 << width) - 1)) as u64
+}
+
+/// A custom sensor table in the custom sensor RAM (0x250-0x3CF)
+///
+/// The table holds `entries` pairs of 24 bit values (6 bytes each) sorted by the first value. The
+/// data pointer of the channel stores the start as 6 bit offset in 4 byte steps from 0x250 and
+/// the number of entries minus 1 in 6 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CustomTable {
+    pub address: u16,
+    pub entries: u8
+}
+
+impl CustomTable {
+    pub fn new(address: u16, entries: u8) -> Self {
+        Self { address, entries }
+    }
+
+    //|11-6| start offset in 4 byte steps, |5-0| entries - 1
+    fn to_bits(self) -> u64 {
+        (u64::from((self.address - CUSTOM_DATA_START) / 4) << 6) | u64::from(self.entries.wrapping_sub(1) & 0x3f)
+    }
+
+    //the start has to be addressable by the 6 bit offset and the entries have to fit the custom sensor RAM
+    fn check(&self) -> Result<(), ConfigError> {
+        if !(CUSTOM_DATA_START..=CUSTOM_DATA_START + 63 * 4).contains(&self.address) {
+            return Err(ConfigError::CustomAddressOutOfRange(self.address));
+        }
+        if !self.address.is_multiple_of(4) {
+            return Err(ConfigError::CustomAddressUnaligned(self.address));
+        }
+        let end = u32::from(self.address) + 6 * u32::from(self.entries);
+        if !(1..=64).contains(&self.entries) || end > u32::from(LAST_REGISTER) + 1 {
+            return Err(ConfigError::CustomTableTooLong { address: self.address, entries: self.entries });
+        }
+        Ok(())
+    }
+
+    fn from_bits(field: u64) -> Self {
+        Self {
+            address: CUSTOM_DATA_START + (field >> 6) as u16 * 4,
+            entries: (field & 0x3f) as u8 + 1
+        }
+    }
+}
+
+//the standard sensor types leave the data pointer at 0
+fn custom_table_from_field(field: u64) -> Option<CustomTable> {
+    match field {
+        0 => None,
+        bits => Some(CustomTable::from_bits(bits))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ThermocoupleParameters {
     cold_junction_channel: Option<LTC2983Channel>,
     sensor_configuration: SensorConfiguration,
     oc_current: LTC2983OcCurrent,
-    custom_address: Option<u16>
-}
-
-impl Default for ThermocoupleParameters {
-    fn default() -> Self {
-        Self { cold_junction_channel: None,
-               sensor_configuration: Default::default(),
-               oc_current: Default::default(),
-               custom_address: None }
-    }
+    custom_table: Option<CustomTable>
 }
 
 impl ThermocoupleParameters {
@@ -99,8 +191,14 @@ impl ThermocoupleParameters {
         self
     }
 
-    pub fn custom_address(mut self, addr: u16) -> Self {
-        self.custom_address = Some(addr);
+    ///custom thermocouple table of `entries` mV/Kelvin pairs at `address`, the channel is then
+    ///configured and reported as [`ThermalProbeType::Thermocouple_Custom`]
+    ///
+    ///The address has to be a multiple of 4 from 0x250 to 0x34C and the 1 to 64 entries of 6 bytes
+    ///have to end at 0x3CF or below, otherwise [`ThermalProbeType::to_config_word`] fails with a
+    ///[`ConfigError`].
+    pub fn custom_table(mut self, address: u16, entries: u8) -> Self {
+        self.custom_table = Some(CustomTable::new(address, entries));
         self
     }
 
@@ -110,25 +208,32 @@ impl ThermocoupleParameters {
     }
 
     pub fn config_to_bits(&self) -> u64 {
-        0x0 | (self.sensor_configuration.identifier() << 3) | self.oc_current.identifier()
+        (self.sensor_configuration.identifier() << 3) | self.oc_current.identifier()
+    }
+
+    fn from_config_word(word: u32) -> Option<Self> {
+        Some(Self {
+            cold_junction_channel: LTC2983Channel::from_identifier(config_field(word, 22, 5)),
+            sensor_configuration: SensorConfiguration::from_identifier(config_field(word, 21, 1)),
+            oc_current: LTC2983OcCurrent::from_identifier(config_field(word, 18, 3))?,
+            //only the custom type uses the data pointer, a table at 0x250 with one entry is all zero
+            custom_table: (config_field(word, 27, 5) == SensorKind::Thermocouple_Custom.type_code())
+                .then(|| CustomTable::from_bits(config_field(word, 0, 12)))
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(non_camel_case_types)]
+///the curve of a platinum RTD, RTD 1000 and NI-120 have a fixed curve and only take the default
 pub enum RTDCurve {
+    #[default]
     EuropeanStandard,
     American,
     Japanese,
     ITS_90
 }
 
-impl Default for RTDCurve {
-    fn default() -> Self {
-        Self::EuropeanStandard
-    }
-}
-
 impl RTDCurve {
     pub fn identifier(&self) -> u64 {
         match self {
@@ -138,13 +243,27 @@ impl RTDCurve {
             RTDCurve::ITS_90            => 3,
         }
     }
+
+    fn from_identifier(identifier: u64) -> Self {
+        match identifier {
+            1 => RTDCurve::American,
+            2 => RTDCurve::Japanese,
+            3 => RTDCurve::ITS_90,
+            _ => RTDCurve::EuropeanStandard
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum RTDWireCount {
+    #[default]
     Wire2,
     Wire3,
     Wire4,
+    ///4-wire RTD with the sense resistor also connected in 4-wire (Kelvin) configuration, so the
+    ///lead and trace resistance of neither is part of the measurement. The sense resistor channel is
+    ///configured as a plain [`ThermalProbeType::SenseResistor`]. Like [`RTDWireCount::Wire4`] this
+    ///mode supports current source rotation.
     Wire4KelvinRsense
 }
 
@@ -159,51 +278,55 @@ impl RTDWireCount {
     }
 }
 
-impl Default for RTDWireCount {
-    fn default() -> Self {
-        Self::Wire2
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RTDSensorConfiguration {
     wire_cnt: RTDWireCount,
     external: bool,
     current_source_rotation: bool
 }
 
-impl Default for RTDSensorConfiguration {
-    fn default() -> Self {
-        Self {
-            wire_cnt: Default::default(),
-            external: false,
-            current_source_rotation: false
-        }
-    }
-}
-
 impl RTDSensorConfiguration {
     pub fn wire_cnt(mut self, wire_cnt: RTDWireCount) -> Self { self.wire_cnt = wire_cnt; self }
+    ///excite the RTD with an external current source, the excitation current of the channel is ignored
     pub fn external(mut self, external: bool) -> Self { self.external = external; self }
     pub fn current_source_rotation(mut self, current_src_rotation: bool) -> Self { self.current_source_rotation = current_src_rotation; self }
 
     pub fn to_bits(&self) -> u64 {
-        let mut bits = 0x0;
-        bits = (bits | self.wire_cnt.identifier()) << 2;
-        if self.current_source_rotation && self.wire_cnt != RTDWireCount::Wire2 && self.wire_cnt != RTDWireCount::Wire3 { // current source rotation is not support in 2 or 3 wire RTDs
-            bits = (bits | 0x1) << 1;
+        // |3-2| wire count, |1-0| excitation mode: 0 = external, 1 = internal, 2 = internal with current source rotation
+        let mode = if self.external {
+            0x0 // an external current source can not be rotated
+        } else if self.current_source_rotation && self.wire_cnt != RTDWireCount::Wire2 && self.wire_cnt != RTDWireCount::Wire3 { // current source rotation is not support in 2 or 3 wire RTDs
+            0x2
         } else {
-            if !self.external {
-                bits = bits | 0x1
-            }
-        }
+            0x1
+        };
+
+        (self.wire_cnt.identifier() << 2) | mode
+    }
+
+    //rotation was asked for, but can not be done with this wiring or current source
+    fn unsupported_rotation(&self) -> bool {
+        self.current_source_rotation && (self.external || matches!(self.wire_cnt, RTDWireCount::Wire2 | RTDWireCount::Wire3))
+    }
 
-        bits
+    ///decode the 4 bit sensor configuration as written by [`RTDSensorConfiguration::to_bits`]
+    pub fn from_bits(bits: u64) -> Self {
+        let wire_cnt = match (bits >> 2) & 0x3 {
+            0 => RTDWireCount::Wire2,
+            1 => RTDWireCount::Wire3,
+            2 => RTDWireCount::Wire4,
+            _ => RTDWireCount::Wire4KelvinRsense
+        };
+        Self { wire_cnt, external: bits & 0x3 == 0x0, current_source_rotation: bits & 0x3 == 0x2 }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum RTDExcitationCurrent {
+    ///supplied by an external current source, see [`RTDSensorConfiguration::external`]
+    External,
+    #[default]
     I5uA,
     I10uA,
     I25uA,
@@ -214,34 +337,60 @@ pub enum RTDExcitationCurrent {
     I1mA
 }
 
-impl Default for RTDExcitationCurrent {
-    fn default() -> Self {
-        Self::I5uA
-    }
-}
-
 impl RTDExcitationCurrent {
     pub fn identifier(&self) -> u64 {
        match self {
-        RTDExcitationCurrent::I5uA   => 1,
-        RTDExcitationCurrent::I10uA  => 2,
-        RTDExcitationCurrent::I25uA  => 3,
-        RTDExcitationCurrent::I50uA  => 4,
-        RTDExcitationCurrent::I100uA => 5,
-        RTDExcitationCurrent::I250uA => 6,
-        RTDExcitationCurrent::I500uA => 7,
-        RTDExcitationCurrent::I1mA   => 8,
+        RTDExcitationCurrent::External => 0,
+        RTDExcitationCurrent::I5uA     => 1,
+        RTDExcitationCurrent::I10uA    => 2,
+        RTDExcitationCurrent::I25uA    => 3,
+        RTDExcitationCurrent::I50uA    => 4,
+        RTDExcitationCurrent::I100uA   => 5,
+        RTDExcitationCurrent::I250uA   => 6,
+        RTDExcitationCurrent::I500uA   => 7,
+        RTDExcitationCurrent::I1mA     => 8,
+    }
+    }
+
+    fn from_identifier(identifier: u64) -> Option<Self> {
+        match identifier {
+            0 => Some(RTDExcitationCurrent::External),
+            1 => Some(RTDExcitationCurrent::I5uA),
+            2 => Some(RTDExcitationCurrent::I10uA),
+            3 => Some(RTDExcitationCurrent::I25uA),
+            4 => Some(RTDExcitationCurrent::I50uA),
+            5 => Some(RTDExcitationCurrent::I100uA),
+            6 => Some(RTDExcitationCurrent::I250uA),
+            7 => Some(RTDExcitationCurrent::I500uA),
+            8 => Some(RTDExcitationCurrent::I1mA),
+            _ => None
+        }
     }
+
+    ///the current of the internal source, 0 for an external source as its current is not known to the device
+    pub fn microamps(&self) -> f32 {
+        match self {
+            RTDExcitationCurrent::External => 0.,
+            RTDExcitationCurrent::I5uA     => 5.,
+            RTDExcitationCurrent::I10uA    => 10.,
+            RTDExcitationCurrent::I25uA    => 25.,
+            RTDExcitationCurrent::I50uA    => 50.,
+            RTDExcitationCurrent::I100uA   => 100.,
+            RTDExcitationCurrent::I250uA   => 250.,
+            RTDExcitationCurrent::I500uA   => 500.,
+            RTDExcitationCurrent::I1mA     => 1000.,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RTDParameters {
     r_sense_channel: LTC2983Channel,
     sensor_configuration: RTDSensorConfiguration,
     excitation_current: RTDExcitationCurrent,
     curve: RTDCurve,
-    custom_address: Option<u16>
+    custom_table: Option<CustomTable>
 }
 
 impl Default for RTDParameters {
@@ -251,12 +400,39 @@ impl Default for RTDParameters {
             sensor_configuration: Default::default(),
             excitation_current: Default::default(),
             curve: Default::default(),
-            custom_address: None
+            custom_table: None
         }
     }
 }
 
 impl RTDParameters {
+    ///default parameters with an excitation current suiting the nominal resistance of the RTD `kind`
+    ///
+    ///The current dissipated in the RTD heats it above the temperature to measure (P = I²R), so
+    ///the current goes down as the resistance goes up: 1mA for a PT-10 down to 50µA for a PT-1000,
+    ///keeping the dissipation at the nominal resistance at or below 12.5µW. The default
+    ///of 5µA is safe for every RTD but gives a small signal on the low resistance types. Other
+    ///sensor kinds get the plain defaults.
+    pub fn for_type(kind: SensorKind) -> Self {
+        let excitation_current = match kind {
+            SensorKind::RTD_PT10                          => RTDExcitationCurrent::I1mA,
+            SensorKind::RTD_PT50                          => RTDExcitationCurrent::I500uA,
+            SensorKind::RTD_PT100 | SensorKind::RTD_NI120 => RTDExcitationCurrent::I250uA,
+            SensorKind::RTD_PT200 | SensorKind::RTD_PT500 => RTDExcitationCurrent::I100uA,
+            SensorKind::RTD_PT1000 | SensorKind::RTD_1000 => RTDExcitationCurrent::I50uA,
+            _ => RTDExcitationCurrent::default()
+        };
+        Self { excitation_current, ..Default::default() }
+    }
+
+    ///power in mW the excitation current dissipates in an RTD of `nominal_resistance` Ohm (P = I²R)
+    ///
+    ///0 for an external current source, its current is not known.
+    pub fn estimated_self_heating_mw(&self, nominal_resistance: f32) -> f32 {
+        let amps = self.excitation_current.microamps() * 1e-6;
+        amps * amps * nominal_resistance * 1e3
+    }
+
     pub fn curve(mut self, curve: RTDCurve) -> Self { self.curve = curve; self}
     pub fn excitation_current(mut self, excitation_current: RTDExcitationCurrent) -> Self { self.excitation_current = excitation_current; self }
     pub fn sensor_configuration(mut self, config: RTDSensorConfiguration) -> Self { self.sensor_configuration = config; self }
@@ -268,20 +444,188 @@ impl RTDParameters {
             self
         }
     }
+
+    fn from_config_word(word: u32) -> Option<Self> {
+        Some(Self {
+            r_sense_channel: LTC2983Channel::from_identifier(config_field(word, 22, 5))?,
+            sensor_configuration: RTDSensorConfiguration::from_bits(config_field(word, 18, 4)),
+            excitation_current: RTDExcitationCurrent::from_identifier(config_field(word, 14, 4))?,
+            curve: RTDCurve::from_identifier(config_field(word, 12, 2)),
+            custom_table: custom_table_from_field(config_field(word, 0, 12))
+        })
+    }
 }
 
-#[derive(Debug)]
-pub enum DiodeReadingCount {
-    READ2,
-    READ3
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum ThermistorExcitationCurrent {
+    I250nA,
+    I500nA,
+    I1uA,
+    I5uA,
+    I10uA,
+    I25uA,
+    I50uA,
+    I100uA,
+    I250uA,
+    I500uA,
+    I1mA,
+    ///the device chooses the current for each conversion
+    #[default]
+    AutoRange
+}
+
+impl ThermistorExcitationCurrent {
+    pub fn identifier(&self) -> u64 {
+        match self {
+            ThermistorExcitationCurrent::I250nA    => 1,
+            ThermistorExcitationCurrent::I500nA    => 2,
+            ThermistorExcitationCurrent::I1uA      => 3,
+            ThermistorExcitationCurrent::I5uA      => 4,
+            ThermistorExcitationCurrent::I10uA     => 5,
+            ThermistorExcitationCurrent::I25uA     => 6,
+            ThermistorExcitationCurrent::I50uA     => 7,
+            ThermistorExcitationCurrent::I100uA    => 8,
+            ThermistorExcitationCurrent::I250uA    => 9,
+            ThermistorExcitationCurrent::I500uA    => 10,
+            ThermistorExcitationCurrent::I1mA      => 11,
+            ThermistorExcitationCurrent::AutoRange => 12,
+        }
+    }
+
+    fn from_identifier(identifier: u64) -> Option<Self> {
+        match identifier {
+            1  => Some(ThermistorExcitationCurrent::I250nA),
+            2  => Some(ThermistorExcitationCurrent::I500nA),
+            3  => Some(ThermistorExcitationCurrent::I1uA),
+            4  => Some(ThermistorExcitationCurrent::I5uA),
+            5  => Some(ThermistorExcitationCurrent::I10uA),
+            6  => Some(ThermistorExcitationCurrent::I25uA),
+            7  => Some(ThermistorExcitationCurrent::I50uA),
+            8  => Some(ThermistorExcitationCurrent::I100uA),
+            9  => Some(ThermistorExcitationCurrent::I250uA),
+            10 => Some(ThermistorExcitationCurrent::I500uA),
+            11 => Some(ThermistorExcitationCurrent::I1mA),
+            12 => Some(ThermistorExcitationCurrent::AutoRange),
+            _  => None
+        }
+    }
+
+    ///the current of the internal source, 0 for auto ranging as the device chooses it for each conversion
+    pub fn microamps(&self) -> f32 {
+        match self {
+            ThermistorExcitationCurrent::I250nA    => 0.25,
+            ThermistorExcitationCurrent::I500nA    => 0.5,
+            ThermistorExcitationCurrent::I1uA      => 1.,
+            ThermistorExcitationCurrent::I5uA      => 5.,
+            ThermistorExcitationCurrent::I10uA     => 10.,
+            ThermistorExcitationCurrent::I25uA     => 25.,
+            ThermistorExcitationCurrent::I50uA     => 50.,
+            ThermistorExcitationCurrent::I100uA    => 100.,
+            ThermistorExcitationCurrent::I250uA    => 250.,
+            ThermistorExcitationCurrent::I500uA    => 500.,
+            ThermistorExcitationCurrent::I1mA      => 1000.,
+            ThermistorExcitationCurrent::AutoRange => 0.,
+        }
+    }
+}
+
+/// Whether the sense resistor of a differential thermistor is shared with other sensors
+///
+/// Sharing lets the current source stay on the thermistor channel, so several thermistors can use one
+/// sense resistor. Current source rotation alternates the direction of the excitation current to cancel
+/// parasitic thermocouple voltages and needs sharing. Rotation measures with the same current in both
+/// directions, so it needs a fixed excitation current and can not be combined with auto ranging. A
+/// single-ended thermistor returns its current through COM, only `NoSharing` is encoded for it and
+/// rotation is not possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThermistorExcitationMode {
+    #[default]
+    NoSharing,
+    Sharing,
+    SharingRotation
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThermistorParameters {
+    r_sense_channel: LTC2983Channel,
+    sensor_configuration: SensorConfiguration,
+    excitation_current: ThermistorExcitationCurrent,
+    excitation_mode: ThermistorExcitationMode
 }
 
-impl Default for DiodeReadingCount {
+impl Default for ThermistorParameters {
     fn default() -> Self {
-        Self::READ2
+        Self {
+            r_sense_channel: LTC2983Channel::CH2,
+            sensor_configuration: Default::default(),
+            excitation_current: Default::default(),
+            excitation_mode: Default::default()
+        }
+    }
+}
+
+impl ThermistorParameters {
+    pub fn excitation_current(mut self, excitation_current: ThermistorExcitationCurrent) -> Self { self.excitation_current = excitation_current; self }
+    pub fn sensor_configuration(mut self, config: SensorConfiguration) -> Self { self.sensor_configuration = config; self }
+    pub fn channel(mut self, channel: LTC2983Channel) -> Self {
+        if channel == LTC2983Channel::CH1 {
+            panic!("CH1 can not be used, because there is no channel 0 and the value here indicates that the resistor is between channel x and x-1!!!!")
+        } else {
+            self.r_sense_channel = channel;
+            self
+        }
+    }
+
+    pub fn excitation_mode(mut self, mode: ThermistorExcitationMode) -> Self { self.excitation_mode = mode; self }
+
+    ///the 3 bit sensor configuration: |2| single-ended, |1| sharing without rotation, |0| sharing with rotation
+    pub fn config_to_bits(&self) -> u64 {
+        match (&self.sensor_configuration, self.excitation_mode) {
+            (SensorConfiguration::SingleEnded, _)                                          => 0b100,
+            (SensorConfiguration::Differential, ThermistorExcitationMode::NoSharing)       => 0b000,
+            (SensorConfiguration::Differential, ThermistorExcitationMode::Sharing)         => 0b010,
+            (SensorConfiguration::Differential, ThermistorExcitationMode::SharingRotation) => 0b001,
+        }
+    }
+
+    //rotation needs both ends of the thermistor on channel inputs
+    fn unsupported_rotation(&self) -> bool {
+        matches!(self.sensor_configuration, SensorConfiguration::SingleEnded) && self.excitation_mode == ThermistorExcitationMode::SharingRotation
+    }
+
+    //rotation needs a fixed current, auto ranging could pick a different one for each direction
+    fn auto_range_with_rotation(&self) -> bool {
+        self.excitation_mode == ThermistorExcitationMode::SharingRotation && matches!(self.excitation_current, ThermistorExcitationCurrent::AutoRange)
+    }
+
+    fn from_config_word(word: u32) -> Option<Self> {
+        Some(Self {
+            r_sense_channel: LTC2983Channel::from_identifier(config_field(word, 22, 5))?,
+            sensor_configuration: SensorConfiguration::from_identifier(config_field(word, 21, 1)),
+            excitation_current: ThermistorExcitationCurrent::from_identifier(config_field(word, 15, 4))?,
+            excitation_mode: match config_field(word, 19, 2) {
+                0b01 => ThermistorExcitationMode::SharingRotation,
+                0b10 => ThermistorExcitationMode::Sharing,
+                _ => ThermistorExcitationMode::NoSharing
+            }
+        })
     }
 }
 
+/// Number of currents the diode voltage is measured with
+///
+/// Every combination with a [`DiodeExcitationCurrent`] is allowed. `READ3` takes an extra reading
+/// to cancel the series resistance of the diode and its leads, at the cost of a longer conversion.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum DiodeReadingCount {
+    ///readings at I and 8I
+    #[default]
+    READ2,
+    ///readings at I, 4I and 8I
+    READ3
+}
+
 impl DiodeReadingCount {
     pub fn identifier(&self) -> u64 {
         match self {
@@ -289,22 +633,27 @@ impl DiodeReadingCount {
             DiodeReadingCount::READ3 => 1,
         }
     }
+
+    fn from_identifier(identifier: u64) -> Self {
+        match identifier {
+            0 => DiodeReadingCount::READ2,
+            _ => DiodeReadingCount::READ3
+        }
+    }
 }
 
-#[derive(Debug)]
+/// The lowest excitation current I of a diode measurement, the other readings use multiples of it
+///
+/// With `I80uA` the highest current is 640µA, all currents are valid with either [`DiodeReadingCount`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum DiodeExcitationCurrent {
+    #[default]
     I10uA,
     I20uA,
     I40uA,
     I80uA
 }
 
-impl Default for DiodeExcitationCurrent {
-    fn default() -> Self {
-        Self::I10uA
-    }
-}
-
 impl DiodeExcitationCurrent {
     pub fn identifier(&self) -> u64 {
         match self {
@@ -314,15 +663,35 @@ impl DiodeExcitationCurrent {
             DiodeExcitationCurrent::I80uA => 3,
         }
     }
+
+    fn from_identifier(identifier: u64) -> Self {
+        match identifier {
+            0 => DiodeExcitationCurrent::I10uA,
+            1 => DiodeExcitationCurrent::I20uA,
+            2 => DiodeExcitationCurrent::I40uA,
+            _ => DiodeExcitationCurrent::I80uA
+        }
+    }
+
+    pub fn microamps(&self) -> f32 {
+        match self {
+            DiodeExcitationCurrent::I10uA => 10.,
+            DiodeExcitationCurrent::I20uA => 20.,
+            DiodeExcitationCurrent::I40uA => 40.,
+            DiodeExcitationCurrent::I80uA => 80.,
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DiodeParameters {
     sensor_configuration: SensorConfiguration,
     num_reading: DiodeReadingCount,
     avg: bool,
     excitation_current: DiodeExcitationCurrent,
-    idealitiy_factor: Option<f32>
+    idealitiy_factor: Option<f32>,
+    offset_c: f32
 }
 
 impl Default for DiodeParameters {
@@ -333,6 +702,7 @@ impl Default for DiodeParameters {
             excitation_current: Default::default(),
             idealitiy_factor: None,
             avg: true,
+            offset_c: 0.,
         }
     }
 }
@@ -353,6 +723,7 @@ impl DiodeParameters {
         self
     }
 
+    ///let the device report a running average of the diode temperature, e.g. for a slowly changing cold junction
     pub fn use_avg(mut self, flag: bool) -> Self {
         self.avg = flag;
         self
@@ -363,8 +734,19 @@ impl DiodeParameters {
         self
     }
 
+    ///correction in °C added to the temperatures read from the diode
+    ///
+    ///The device has no register for an offset, the driver adds it to valid and suspect results after
+    ///reading them. It is not part of the configuration word, so it is lost when the device
+    ///configuration is read back, and the device uses the uncorrected diode temperature when the
+    ///diode is the cold junction of a thermocouple.
+    pub fn offset_c(mut self, offset: f32) -> Self {
+        self.offset_c = offset;
+        self
+    }
+
     pub fn to_bits(&self) -> u64 {
-        0x0 | (self.sensor_configuration.identifier() << 26)
+        (self.sensor_configuration.identifier() << 26)
             | (self.num_reading.identifier() << 25)
             | ((self.avg as u64) << 24)
             | (self.excitation_current.identifier() << 22)
@@ -376,11 +758,195 @@ impl DiodeParameters {
                 }
             })
     }
+
+    fn from_config_word(word: u32) -> Self {
+        Self {
+            sensor_configuration: SensorConfiguration::from_identifier(config_field(word, 26, 1)),
+            num_reading: DiodeReadingCount::from_identifier(config_field(word, 25, 1)),
+            avg: config_field(word, 24, 1) == 1,
+            excitation_current: DiodeExcitationCurrent::from_identifier(config_field(word, 22, 2)),
+            idealitiy_factor: match config_field(word, 0, 22) {
+                0 => None,
+                factor => Some(FixedU32::<U20>::from_bits(factor as u32).to_num())
+            },
+            offset_c: 0.
+        }
+    }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectADCParameters {
+    sensor_configuration: SensorConfiguration
+}
+
+impl DirectADCParameters {
+    pub fn sensor_configuration(mut self, config: SensorConfiguration) -> Self {
+        self.sensor_configuration = config;
+        self
+    }
+
+    pub fn to_bits(&self) -> u64 {
+        self.sensor_configuration.identifier() << 26
+    }
+
+    fn from_config_word(word: u32) -> Self {
+        Self { sensor_configuration: SensorConfiguration::from_identifier(config_field(word, 26, 1)) }
+    }
+}
 
+/// The sensor type of a [`ThermalProbeType`] without its parameters
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SensorKind {
+    Thermocouple_J,
+    Thermocouple_K,
+    Thermocouple_E,
+    Thermocouple_N,
+    Thermocouple_R,
+    Thermocouple_S,
+    Thermocouple_T,
+    Thermocouple_B,
+    Thermocouple_Custom,
+    RTD_PT10,
+    RTD_PT50,
+    RTD_PT100,
+    RTD_PT200,
+    RTD_PT500,
+    RTD_PT1000,
+    RTD_1000,
+    RTD_NI120,
+    Thermistor_44004_44033,
+    Thermistor_44005_44030,
+    Thermistor_44007_44034,
+    Thermistor_44006_44031,
+    Thermistor_44008_44032,
+    Thermistor_YSI400,
+    Thermistor_Spectrum,
+    Diode,
+    SenseResistor,
+    DirectADC
+}
+
+impl SensorKind {
+    ///the sensor type code written to the upper 5 bits of the channel configuration word
+    pub fn type_code(&self) -> u64 {
+        match self {
+            SensorKind::Thermocouple_J         => 1,
+            SensorKind::Thermocouple_K         => 2,
+            SensorKind::Thermocouple_E         => 3,
+            SensorKind::Thermocouple_N         => 4,
+            SensorKind::Thermocouple_R         => 5,
+            SensorKind::Thermocouple_S         => 6,
+            SensorKind::Thermocouple_T         => 7,
+            SensorKind::Thermocouple_B         => 8,
+            SensorKind::Thermocouple_Custom    => 9,
+            SensorKind::RTD_PT10               => 10,
+            SensorKind::RTD_PT50               => 11,
+            SensorKind::RTD_PT100              => 12,
+            SensorKind::RTD_PT200              => 13,
+            SensorKind::RTD_PT500              => 14,
+            SensorKind::RTD_PT1000             => 15,
+            SensorKind::RTD_1000               => 16,
+            SensorKind::RTD_NI120              => 17,
+            SensorKind::Thermistor_44004_44033 => 19,
+            SensorKind::Thermistor_44005_44030 => 20,
+            SensorKind::Thermistor_44007_44034 => 21,
+            SensorKind::Thermistor_44006_44031 => 22,
+            SensorKind::Thermistor_44008_44032 => 23,
+            SensorKind::Thermistor_YSI400      => 24,
+            SensorKind::Thermistor_Spectrum    => 25,
+            SensorKind::Diode                  => 28,
+            SensorKind::SenseResistor          => 29,
+            SensorKind::DirectADC              => 30
+        }
+    }
+
+    ///the sensor type of a type code, `None` for unassigned channels (0) and unused codes
+    pub fn from_type_code(code: u64) -> Option<Self> {
+        let kind = match code {
+            1  => SensorKind::Thermocouple_J,
+            2  => SensorKind::Thermocouple_K,
+            3  => SensorKind::Thermocouple_E,
+            4  => SensorKind::Thermocouple_N,
+            5  => SensorKind::Thermocouple_R,
+            6  => SensorKind::Thermocouple_S,
+            7  => SensorKind::Thermocouple_T,
+            8  => SensorKind::Thermocouple_B,
+            9  => SensorKind::Thermocouple_Custom,
+            10 => SensorKind::RTD_PT10,
+            11 => SensorKind::RTD_PT50,
+            12 => SensorKind::RTD_PT100,
+            13 => SensorKind::RTD_PT200,
+            14 => SensorKind::RTD_PT500,
+            15 => SensorKind::RTD_PT1000,
+            16 => SensorKind::RTD_1000,
+            17 => SensorKind::RTD_NI120,
+            19 => SensorKind::Thermistor_44004_44033,
+            20 => SensorKind::Thermistor_44005_44030,
+            21 => SensorKind::Thermistor_44007_44034,
+            22 => SensorKind::Thermistor_44006_44031,
+            23 => SensorKind::Thermistor_44008_44032,
+            24 => SensorKind::Thermistor_YSI400,
+            25 => SensorKind::Thermistor_Spectrum,
+            28 => SensorKind::Diode,
+            29 => SensorKind::SenseResistor,
+            30 => SensorKind::DirectADC,
+            _  => return None
+        };
+        Some(kind)
+    }
+
+    ///the sensor type of a channel configuration word read back from the device
+    ///
+    ///Only the kind can be recovered without knowing the parameters, see [`LTC2983::read_channel_kinds`].
+    pub fn from_config_word(word: u32) -> Option<Self> {
+        Self::from_type_code(config_field(word, 27, 5))
+    }
+
+    ///name of the sensor type
+    pub fn name(&self) -> &'static str {
+        match self {
+            SensorKind::Thermocouple_J         => "Thermocouple J",
+            SensorKind::Thermocouple_K         => "Thermocouple K",
+            SensorKind::Thermocouple_E         => "Thermocouple E",
+            SensorKind::Thermocouple_N         => "Thermocouple N",
+            SensorKind::Thermocouple_R         => "Thermocouple R",
+            SensorKind::Thermocouple_S         => "Thermocouple S",
+            SensorKind::Thermocouple_T         => "Thermocouple T",
+            SensorKind::Thermocouple_B         => "Thermocouple B",
+            SensorKind::Thermocouple_Custom    => "Thermocouple Custom",
+            SensorKind::RTD_PT10               => "RTD PT-10",
+            SensorKind::RTD_PT50               => "RTD PT-50",
+            SensorKind::RTD_PT100              => "RTD PT-100",
+            SensorKind::RTD_PT200              => "RTD PT-200",
+            SensorKind::RTD_PT500              => "RTD PT-500",
+            SensorKind::RTD_PT1000             => "RTD PT-1000",
+            SensorKind::RTD_1000               => "RTD 1000",
+            SensorKind::RTD_NI120              => "RTD NI-120",
+            SensorKind::Thermistor_44004_44033 => "Thermistor 44004/44033",
+            SensorKind::Thermistor_44005_44030 => "Thermistor 44005/44030",
+            SensorKind::Thermistor_44007_44034 => "Thermistor 44007/44034",
+            SensorKind::Thermistor_44006_44031 => "Thermistor 44006/44031",
+            SensorKind::Thermistor_44008_44032 => "Thermistor 44008/44032",
+            SensorKind::Thermistor_YSI400      => "Thermistor YSI-400",
+            SensorKind::Thermistor_Spectrum    => "Thermistor Spectrum 1003k",
+            SensorKind::Diode                  => "Diode",
+            SensorKind::SenseResistor          => "Sense Resistor",
+            SensorKind::DirectADC              => "Direct ADC"
+        }
+    }
+}
+
+impl From<SensorKind> for u64 {
+    fn from(kind: SensorKind) -> Self {
+        kind.type_code()
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ThermalProbeType {
     Thermocouple_J(ThermocoupleParameters),
     Thermocouple_K(ThermocoupleParameters),
@@ -390,6 +956,7 @@ pub enum ThermalProbeType {
     Thermocouple_S(ThermocoupleParameters),
     Thermocouple_T(ThermocoupleParameters),
     Thermocouple_B(ThermocoupleParameters),
+    Thermocouple_Custom(ThermocoupleParameters),
     RTD_PT10(RTDParameters),
     RTD_PT50(RTDParameters),
     RTD_PT100(RTDParameters),
@@ -398,269 +965,103 @@ pub enum ThermalProbeType {
     RTD_PT1000(RTDParameters),
     RTD_1000(RTDParameters),
     RTD_NI120(RTDParameters),
-    Thermistor_44004_44033,
-    Thermistor_44005_44030,
-    Thermistor_44007_44034,
-    Thermistor_44006_44031,
-    Thermistor_44008_44032,
-    Thermistor_YSI400,
-    Thermistor_Spectrum,
+    Thermistor_44004_44033(ThermistorParameters),
+    Thermistor_44005_44030(ThermistorParameters),
+    Thermistor_44007_44034(ThermistorParameters),
+    Thermistor_44006_44031(ThermistorParameters),
+    Thermistor_44008_44032(ThermistorParameters),
+    Thermistor_YSI400(ThermistorParameters),
+    ///Spectrum 1003K (10kΩ at 25°C), sensor type 25. The device has its own curve for it, the
+    ///default auto ranging excitation suits it like the 44000 series.
+    Thermistor_Spectrum(ThermistorParameters),
     Diode(DiodeParameters),
-    SenseResistor(f32)
+    SenseResistor(f32),
+    DirectADC(DirectADCParameters)
 }
 
 impl ThermalProbeType {
     pub fn identifier(&self) -> u64 {
+        self.kind().type_code()
+    }
+
+    ///the sensor type without its parameters
+    ///
+    ///A thermocouple with a custom table is a custom thermocouple, whatever its variant.
+    pub fn kind(&self) -> SensorKind {
+        if self.thermocouple_parameters().is_some_and(|param| param.custom_table.is_some()) {
+            return SensorKind::Thermocouple_Custom;
+        }
         match self {
-            ThermalProbeType::Thermocouple_J(_)      => 1,
-            ThermalProbeType::Thermocouple_K(_)      => 2,
-            ThermalProbeType::Thermocouple_E(_)      => 3,
-            ThermalProbeType::Thermocouple_N(_)      => 4,
-            ThermalProbeType::Thermocouple_R(_)      => 5,
-            ThermalProbeType::Thermocouple_S(_)      => 6,
-            ThermalProbeType::Thermocouple_T(_)      => 7,
-            ThermalProbeType::Thermocouple_B(_)      => 8,
-            ThermalProbeType::RTD_PT10(_)            => 10,
-            ThermalProbeType::RTD_PT50(_)            => 11,
-            ThermalProbeType::RTD_PT100(_)           => 12,
-            ThermalProbeType::RTD_PT200(_)           => 13,
-            ThermalProbeType::RTD_PT500(_)           => 14,
-            ThermalProbeType::RTD_PT1000(_)          => 15,
-            ThermalProbeType::RTD_1000(_)            => 16,
-            ThermalProbeType::RTD_NI120(_)           => 17,
-            ThermalProbeType::Thermistor_44004_44033 => 19,
-            ThermalProbeType::Thermistor_44005_44030 => 20,
-            ThermalProbeType::Thermistor_44007_44034 => 21,
-            ThermalProbeType::Thermistor_44006_44031 => 22,
-            ThermalProbeType::Thermistor_44008_44032 => 23,
-            ThermalProbeType::Thermistor_YSI400      => 24,
-            ThermalProbeType::Thermistor_Spectrum    => 25,
-            ThermalProbeType::Diode(_)               => 28,
-            ThermalProbeType::SenseResistor(_)       => 29
+            ThermalProbeType::Thermocouple_J(_)         => SensorKind::Thermocouple_J,
+            ThermalProbeType::Thermocouple_K(_)         => SensorKind::Thermocouple_K,
+            ThermalProbeType::Thermocouple_E(_)         => SensorKind::Thermocouple_E,
+            ThermalProbeType::Thermocouple_N(_)         => SensorKind::Thermocouple_N,
+            ThermalProbeType::Thermocouple_R(_)         => SensorKind::Thermocouple_R,
+            ThermalProbeType::Thermocouple_S(_)         => SensorKind::Thermocouple_S,
+            ThermalProbeType::Thermocouple_T(_)         => SensorKind::Thermocouple_T,
+            ThermalProbeType::Thermocouple_B(_)         => SensorKind::Thermocouple_B,
+            ThermalProbeType::Thermocouple_Custom(_)    => SensorKind::Thermocouple_Custom,
+            ThermalProbeType::RTD_PT10(_)               => SensorKind::RTD_PT10,
+            ThermalProbeType::RTD_PT50(_)               => SensorKind::RTD_PT50,
+            ThermalProbeType::RTD_PT100(_)              => SensorKind::RTD_PT100,
+            ThermalProbeType::RTD_PT200(_)              => SensorKind::RTD_PT200,
+            ThermalProbeType::RTD_PT500(_)              => SensorKind::RTD_PT500,
+            ThermalProbeType::RTD_PT1000(_)             => SensorKind::RTD_PT1000,
+            ThermalProbeType::RTD_1000(_)               => SensorKind::RTD_1000,
+            ThermalProbeType::RTD_NI120(_)              => SensorKind::RTD_NI120,
+            ThermalProbeType::Thermistor_44004_44033(_) => SensorKind::Thermistor_44004_44033,
+            ThermalProbeType::Thermistor_44005_44030(_) => SensorKind::Thermistor_44005_44030,
+            ThermalProbeType::Thermistor_44007_44034(_) => SensorKind::Thermistor_44007_44034,
+            ThermalProbeType::Thermistor_44006_44031(_) => SensorKind::Thermistor_44006_44031,
+            ThermalProbeType::Thermistor_44008_44032(_) => SensorKind::Thermistor_44008_44032,
+            ThermalProbeType::Thermistor_YSI400(_)      => SensorKind::Thermistor_YSI400,
+            ThermalProbeType::Thermistor_Spectrum(_)    => SensorKind::Thermistor_Spectrum,
+            ThermalProbeType::Diode(_)                  => SensorKind::Diode,
+            ThermalProbeType::SenseResistor(_)          => SensorKind::SenseResistor,
+            ThermalProbeType::DirectADC(_)              => SensorKind::DirectADC,
         }
     }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub enum LTC2983Result {
-    Invalid(u8),
-    Suspect(f32, u8),
-    Valid(f32)
-}
 
-impl From<[u8; 4]> for LTC2983Result {
-    fn from(bytes: [u8; 4]) -> Self {
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(bytes[1..=3].try_into().unwrap()));
-        let error_code = bytes[0];
-        if error_code == 0x01 { // indicates valid result
-            LTC2983Result::Valid(value.to_num())
-        } else if error_code & 0xe != 0 { //if any of the upper three bits of the error code are set then the result is invalid
-            LTC2983Result::Invalid(error_code)
-        } else { // in all other cases the reading should regarded as suspect
-            LTC2983Result::Suspect(value.to_num(), error_code)
+    ///the 32 bit word for the channel configuration register, computed without writing it
+    ///
+    ///Fails for settings the device does not support and for values that do not fit their field.
+    pub fn to_config_word(&self) -> Result<u32, ConfigError> {
+        if self.rtd_parameters().is_some_and(|param| param.sensor_configuration.unsupported_rotation()) {
+            return Err(ConfigError::UnsupportedRotation);
+        }
+        if self.thermistor_parameters().is_some_and(ThermistorParameters::unsupported_rotation) {
+            return Err(ConfigError::UnsupportedThermistorRotation);
         }
+        if self.thermistor_parameters().is_some_and(ThermistorParameters::auto_range_with_rotation) {
+            return Err(ConfigError::AutoRangeWithRotation);
+        }
+        match self {
+            //only the platinum RTDs use the curve field, the others need it left at 0
+            ThermalProbeType::RTD_1000(param) | ThermalProbeType::RTD_NI120(param) if !matches!(param.curve, RTDCurve::EuropeanStandard) => {
+                return Err(ConfigError::InvalidRTDCurve);
+            }
+            //17 integer bits
+            ThermalProbeType::SenseResistor(resistance) if !(0. ..131072.).contains(resistance) => {
+                return Err(ConfigError::SenseResistanceOutOfRange(*resistance));
+            }
+            //2 integer bits
+            ThermalProbeType::Diode(DiodeParameters { idealitiy_factor: Some(factor), .. }) if !(0. ..4.).contains(factor) => {
+                return Err(ConfigError::IdealityFactorOutOfRange(*factor));
+            }
+            _ => {}
+        }
+        //the data pointer has to point into the custom sensor RAM
+        let custom_table = self.thermocouple_parameters().and_then(|param| param.custom_table)
+            .or(self.rtd_parameters().and_then(|param| param.custom_table));
+        if let Some(table) = custom_table {
+            table.check()?;
+        }
+        Ok(self.pack_config_word())
     }
-}
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum LTC2983Channel {
-    CH1,
-    CH2,
-    CH3,
-    CH4,
-    CH5,
-    CH6,
-    CH7,
-    CH8,
-    CH9,
-    CH10,
-    CH11,
-    CH12,
-    CH13,
-    CH14,
-    CH15,
-    CH16,
-    CH17,
-    CH18,
-    CH19,
-    CH20
-}
-
-impl LTC2983Channel {
-    pub fn start_address(&self) -> u16 {
-        match self {
-            LTC2983Channel::CH1  => 0x200,
-            LTC2983Channel::CH2  => 0x204,
-            LTC2983Channel::CH3  => 0x208,
-            LTC2983Channel::CH4  => 0x20C,
-            LTC2983Channel::CH5  => 0x210,
-            LTC2983Channel::CH6  => 0x214,
-            LTC2983Channel::CH7  => 0x218,
-            LTC2983Channel::CH8  => 0x21C,
-            LTC2983Channel::CH9  => 0x220,
-            LTC2983Channel::CH10 => 0x224,
-            LTC2983Channel::CH11 => 0x228,
-            LTC2983Channel::CH12 => 0x22C,
-            LTC2983Channel::CH13 => 0x230,
-            LTC2983Channel::CH14 => 0x234,
-            LTC2983Channel::CH15 => 0x238,
-            LTC2983Channel::CH16 => 0x23C,
-            LTC2983Channel::CH17 => 0x240,
-            LTC2983Channel::CH18 => 0x244,
-            LTC2983Channel::CH19 => 0x248,
-            LTC2983Channel::CH20 => 0x24C
-        }
-    }
-
-    pub fn result_address(&self) -> u16 {
-        match self {
-            LTC2983Channel::CH1  => 0x010,
-            LTC2983Channel::CH2  => 0x014,
-            LTC2983Channel::CH3  => 0x018,
-            LTC2983Channel::CH4  => 0x01C,
-            LTC2983Channel::CH5  => 0x020,
-            LTC2983Channel::CH6  => 0x024,
-            LTC2983Channel::CH7  => 0x028,
-            LTC2983Channel::CH8  => 0x02C,
-            LTC2983Channel::CH9  => 0x030,
-            LTC2983Channel::CH10 => 0x034,
-            LTC2983Channel::CH11 => 0x038,
-            LTC2983Channel::CH12 => 0x03C,
-            LTC2983Channel::CH13 => 0x040,
-            LTC2983Channel::CH14 => 0x044,
-            LTC2983Channel::CH15 => 0x048,
-            LTC2983Channel::CH16 => 0x04C,
-            LTC2983Channel::CH17 => 0x050,
-            LTC2983Channel::CH18 => 0x054,
-            LTC2983Channel::CH19 => 0x058,
-            LTC2983Channel::CH20 => 0x05C,
-        }
-    }
-
-    pub fn identifier(&self) -> u64 {
-        match self {
-            LTC2983Channel::CH1  => 1,
-            LTC2983Channel::CH2  => 2,
-            LTC2983Channel::CH3  => 3,
-            LTC2983Channel::CH4  => 4,
-            LTC2983Channel::CH5  => 5,
-            LTC2983Channel::CH6  => 6,
-            LTC2983Channel::CH7  => 7,
-            LTC2983Channel::CH8  => 8,
-            LTC2983Channel::CH9  => 9,
-            LTC2983Channel::CH10 => 10,
-            LTC2983Channel::CH11 => 11,
-            LTC2983Channel::CH12 => 12,
-            LTC2983Channel::CH13 => 13,
-            LTC2983Channel::CH14 => 14,
-            LTC2983Channel::CH15 => 15,
-            LTC2983Channel::CH16 => 16,
-            LTC2983Channel::CH17 => 17,
-            LTC2983Channel::CH18 => 18,
-            LTC2983Channel::CH19 => 19,
-            LTC2983Channel::CH20 => 20,
-        }
-    }
-
-    pub fn mask(&self) -> u32 {
-       0x1 << (self.identifier() - 1)
-    }
-}
-
-#[derive(Debug)]
-#[allow(dead_code)]
-pub struct LTC2983Status {
-    start: bool,
-    done: bool,
-    //1 bit unused
-    channel_selection: u8
-}
-
-impl LTC2983Status {
-    pub fn done(&self) -> bool {
-        self.done
-    }
-}
-
-impl From<u8> for LTC2983Status {
-    fn from(data: u8) -> Self {
-        LTC2983Status {
-            start: data & 0x80 == 0x80,
-            done: data & 0x40 == 0x40,
-            channel_selection: data & 0x1f
-        }
-    }
-}
-
-#[derive(Debug)]
-pub enum LTC2983OcCurrent {
-    External,
-    I10uA,
-    I100uA,
-    I500uA,
-    I1mA
-}
-
-impl Default for LTC2983OcCurrent {
-    fn default() -> Self {
-        Self::I10uA
-    }
-}
-
-impl LTC2983OcCurrent {
-    pub fn identifier(&self) -> u64 {
+    //pack the configuration word, the values have to be checked to fit their fields
+    fn pack_config_word(&self) -> u32 {
         match self {
-            LTC2983OcCurrent::External => 0,
-            LTC2983OcCurrent::I10uA => 4,
-            LTC2983OcCurrent::I100uA => 5,
-            LTC2983OcCurrent::I500uA => 6,
-            LTC2983OcCurrent::I1mA => 7,
-        }
-    }
-}
-
-#[derive(Debug, Error)]
-pub enum LTC2983Error<SPI> {
-    #[error("SPI communication error: {0:?}")]
-    SpiError(#[from] SPI),
-    #[error("Channel {0:?} not configured!")]
-    ChannelUnconfigured(LTC2983Channel),
-    #[error("Error while calculating average from mutliple rounds of readouts.")]
-    AvgCalculationError
-}
-
-pub struct LTC2983<SPI> {
-    spi_device: SPI,
-}
-
-impl<SPI> LTC2983<SPI> where SPI: SpiDevice {
-    pub fn new(spi_device: SPI) -> Self {
-        LTC2983 { spi_device }
-    }
-
-    //read device satatus
-    pub fn status(&mut self) -> Result<LTC2983Status, LTC2983Error<SPI::Error>> {
-        let mut read_status_bytes = ByteBuffer::new();
-        read_status_bytes.write_u8(LTC2983_READ);
-        read_status_bytes.write_u16(STATUS_REGISTER);
-        read_status_bytes.write_u8(0x0); //Dummy Data
-
-        let mut recv: [u8; 4] = [0, 0, 0, 0];
-        match self.spi_device.transfer(&mut recv, read_status_bytes.as_bytes()) {
-            Ok(_) => {
-                Ok(LTC2983Status::from(recv[3]))
-            }
-            Err(err) => Err(LTC2983Error::SpiError(err))
-        }
-
-    }
-
-    //write channel configuration
-    pub fn setup_channel(&mut self,
-                         probe: ThermalProbeType,
-                         channel: &LTC2983Channel) -> Result<(), LTC2983Error<SPI::Error>>
-    {
-        match &probe {
             ThermalProbeType::Thermocouple_J(param) |
             ThermalProbeType::Thermocouple_K(param) |
             ThermalProbeType::Thermocouple_E(param) |
@@ -668,13 +1069,12 @@ impl<SPI> LTC2983<SPI> where SPI: SpiDevice {
             ThermalProbeType::Thermocouple_R(param) |
             ThermalProbeType::Thermocouple_S(param) |
             ThermalProbeType::Thermocouple_T(param) |
-            ThermalProbeType::Thermocouple_B(param) => {
-                let mut write_sequence = ByteBuffer::new();
-                write_sequence.write_u8(LTC2983_WRITE);              //the first byte of the communication indicates a read or write operation
-                write_sequence.write_u16(channel.start_address());   //the second two bytes hold the address to ẁrite to
+            ThermalProbeType::Thermocouple_B(param) |
+            ThermalProbeType::Thermocouple_Custom(param) => {
+                let mut write_sequence = word_buffer();
                 // The 32 bit data to be written to the channel configuration register has the following format for thermocouples
-                // |31-27| Thermocouple Type
-                write_sequence.write_bits(probe.identifier(), 5);
+                // |31-27| Thermocouple Type, the data pointer is only used by the custom thermocouple type
+                write_sequence.write_bits(self.identifier(), 5);
                 // |26-22| Could Junction Channel ID -> if no cold junction compensation is used this value will be 0
                 write_sequence.write_bits(match &param.cold_junction_channel { None => 0, Some(chan) => chan.identifier() }, 5);
                 // |21-18| Sensor Configuration
@@ -682,10 +1082,9 @@ impl<SPI> LTC2983<SPI> where SPI: SpiDevice {
                 // |17-12| Unused => equals 0
                 write_sequence.write_bits(0, 6);
                 // |11-0| Custom Thermocouple Data Pointer
-                write_sequence.write_bits(match &param.custom_address { None => 0, Some(addr) => *addr}.into(), 12);
+                write_sequence.write_bits(param.custom_table.map_or(0, CustomTable::to_bits), 12);
 
-                self.spi_device.write(write_sequence.as_bytes())?;
-                Ok(())
+                write_sequence.read_u32().unwrap()
             }
             ThermalProbeType::RTD_PT10(param)   |
             ThermalProbeType::RTD_PT50(param)   |
@@ -695,277 +1094,5060 @@ impl<SPI> LTC2983<SPI> where SPI: SpiDevice {
             ThermalProbeType::RTD_PT1000(param) |
             ThermalProbeType::RTD_1000(param)   |
             ThermalProbeType::RTD_NI120(param)  => {
-                let mut write_sequence = ByteBuffer::new();
-                write_sequence.write_u8(LTC2983_WRITE);              //the first byte of the communication indicates a read or write operation
-                write_sequence.write_u16(channel.start_address());   //the second two bytes hold the address to ẁrite to
+                let mut write_sequence = word_buffer();
                 // The 32 bit data to be written to the channel configuration register has the following format for thermocouples
                 // |31-27| RTD Type
-                write_sequence.write_bits(probe.identifier(), 5);
+                write_sequence.write_bits(self.identifier(), 5);
                 // |26-22| Rsense Channel Assignment
                 write_sequence.write_bits(param.r_sense_channel.identifier(), 5);
                 // |21-18| Sensor Configuration
                 write_sequence.write_bits(param.sensor_configuration.to_bits(), 4);
-                // |17-14| Excitation Current
-                write_sequence.write_bits(param.excitation_current.identifier(), 4);
+                // |17-14| Excitation Current, zero when excited externally
+                write_sequence.write_bits(match param.sensor_configuration.external {
+                    true => RTDExcitationCurrent::External.identifier(),
+                    false => param.excitation_current.identifier()
+                }, 4);
                 // |13-12| Curve
                 write_sequence.write_bits(param.curve.identifier(), 2);
                 // |11-0| Custom RTD Data Pointer
-                write_sequence.write_bits(match &param.custom_address { None => 0, Some(addr) => *addr}.into(), 12);
+                write_sequence.write_bits(param.custom_table.map_or(0, CustomTable::to_bits), 12);
 
-                self.spi_device.write(write_sequence.as_bytes())?;
-                Ok(())
+                write_sequence.read_u32().unwrap()
             }
-            ThermalProbeType::Thermistor_44004_44033 |
-            ThermalProbeType::Thermistor_44005_44030 |
-            ThermalProbeType::Thermistor_44007_44034 |
-            ThermalProbeType::Thermistor_44006_44031 |
-            ThermalProbeType::Thermistor_44008_44032 |
-            ThermalProbeType::Thermistor_YSI400      |
-            ThermalProbeType::Thermistor_Spectrum    => {
-                unimplemented!();
+            ThermalProbeType::Thermistor_44004_44033(param) |
+            ThermalProbeType::Thermistor_44005_44030(param) |
+            ThermalProbeType::Thermistor_44007_44034(param) |
+            ThermalProbeType::Thermistor_44006_44031(param) |
+            ThermalProbeType::Thermistor_44008_44032(param) |
+            ThermalProbeType::Thermistor_YSI400(param)      |
+            ThermalProbeType::Thermistor_Spectrum(param)    => {
+                let mut write_sequence = word_buffer();
+                // The 32 bit data to be written to the channel configuration register has the following format for thermistors
+                // |31-27| Thermistor Type
+                write_sequence.write_bits(self.identifier(), 5);
+                // |26-22| Rsense Channel Assignment
+                write_sequence.write_bits(param.r_sense_channel.identifier(), 5);
+                // |21-19| Sensor Configuration
+                write_sequence.write_bits(param.config_to_bits(), 3);
+                // |18-15| Excitation Current
+                write_sequence.write_bits(param.excitation_current.identifier(), 4);
+                // |14-0| Unused => equals 0
+                write_sequence.write_bits(0, 15);
+
+                write_sequence.read_u32().unwrap()
             }
             ThermalProbeType::Diode(param) => {
-                let mut write_sequence = ByteBuffer::new();
-                write_sequence.write_u8(LTC2983_WRITE);              //the first byte of the communication indicates a read or write operation
-                write_sequence.write_u16(channel.start_address());   //the second two bytes hold the address to ẁrite to
-                write_sequence.write_bits(probe.identifier(), 5);
+                let mut write_sequence = word_buffer();
+                write_sequence.write_bits(self.identifier(), 5);
                 write_sequence.write_bits(param.to_bits(), 27);
 
-                self.spi_device.write(write_sequence.as_bytes())?;
-                Ok(())
+                write_sequence.read_u32().unwrap()
+            }
+            ThermalProbeType::DirectADC(param) => {
+                let mut write_sequence = word_buffer();
+                // |31-27| Sensor Type
+                write_sequence.write_bits(self.identifier(), 5);
+                // |26| Single-Ended / Differential, |25-0| Unused => equals 0
+                write_sequence.write_bits(param.to_bits(), 27);
+
+                write_sequence.read_u32().unwrap()
             }
             ThermalProbeType::SenseResistor(resistance) => {
-                let mut write_sequence = ByteBuffer::new();
-                write_sequence.write_u8(LTC2983_WRITE);              //the first byte of the communication indicates a read or write operation
-                write_sequence.write_u16(channel.start_address());   //the second two bytes hold the address to ẁrite to
+                let mut write_sequence = word_buffer();
                 // The 32 bit data to be written to the channel configuration register has the following format for sense resistors
                 // |31-27| Thermocouple Type
-                write_sequence.write_bits(probe.identifier(), 5);
+                write_sequence.write_bits(self.identifier(), 5);
                 // |26-0| Fixed Point Floating point (17,10) no sign bit representing the resistance
                 let resistance_fixed_point = FixedU32::<U10>::from_num(*resistance);
                 write_sequence.write_bits(resistance_fixed_point.to_bits().into(), 27);
 
-                self.spi_device.write(write_sequence.as_bytes())?;
-                Ok(())
+                write_sequence.read_u32().unwrap()
             }
         }
     }
 
-    //check if the channel is configured
-    pub fn channel_enabled(&mut self, channel: &LTC2983Channel) -> bool {
-        let mut read_sequence = ByteBuffer::new();
-        read_sequence.write_u8(LTC2983_READ);
-        read_sequence.write_u16(channel.start_address());
-        read_sequence.write_u8(0); //Dummy Data for read
-
-        let mut recv: [u8; 4] = [0, 0, 0, 0];
-        match self.spi_device.transfer(&mut recv, read_sequence.as_bytes()) {
-            Ok(_) => {
-                //if the upper 5bits of the channel are zero, then the channel is disabled so checking for not zero means the channel is enabled
-                if recv[3] & 0xf8 != 0 {
-                    true
-                } else {
-                    false
-                }
-            }
-            Err(_err) => {
-                //on communication error assume unconfigured channel
-                false
-            }
+    //decode a channel configuration word, `None` for unassigned channels and invalid words
+    fn from_config_word(word: u32) -> Option<Self> {
+        let probe = match config_field(word, 27, 5) {
+            1  => ThermalProbeType::Thermocouple_J(ThermocoupleParameters::from_config_word(word)?),
+            2  => ThermalProbeType::Thermocouple_K(ThermocoupleParameters::from_config_word(word)?),
+            3  => ThermalProbeType::Thermocouple_E(ThermocoupleParameters::from_config_word(word)?),
+            4  => ThermalProbeType::Thermocouple_N(ThermocoupleParameters::from_config_word(word)?),
+            5  => ThermalProbeType::Thermocouple_R(ThermocoupleParameters::from_config_word(word)?),
+            6  => ThermalProbeType::Thermocouple_S(ThermocoupleParameters::from_config_word(word)?),
+            7  => ThermalProbeType::Thermocouple_T(ThermocoupleParameters::from_config_word(word)?),
+            8  => ThermalProbeType::Thermocouple_B(ThermocoupleParameters::from_config_word(word)?),
+            9  => ThermalProbeType::Thermocouple_Custom(ThermocoupleParameters::from_config_word(word)?),
+            10 => ThermalProbeType::RTD_PT10(RTDParameters::from_config_word(word)?),
+            11 => ThermalProbeType::RTD_PT50(RTDParameters::from_config_word(word)?),
+            12 => ThermalProbeType::RTD_PT100(RTDParameters::from_config_word(word)?),
+            13 => ThermalProbeType::RTD_PT200(RTDParameters::from_config_word(word)?),
+            14 => ThermalProbeType::RTD_PT500(RTDParameters::from_config_word(word)?),
+            15 => ThermalProbeType::RTD_PT1000(RTDParameters::from_config_word(word)?),
+            16 => ThermalProbeType::RTD_1000(RTDParameters::from_config_word(word)?),
+            17 => ThermalProbeType::RTD_NI120(RTDParameters::from_config_word(word)?),
+            19 => ThermalProbeType::Thermistor_44004_44033(ThermistorParameters::from_config_word(word)?),
+            20 => ThermalProbeType::Thermistor_44005_44030(ThermistorParameters::from_config_word(word)?),
+            21 => ThermalProbeType::Thermistor_44007_44034(ThermistorParameters::from_config_word(word)?),
+            22 => ThermalProbeType::Thermistor_44006_44031(ThermistorParameters::from_config_word(word)?),
+            23 => ThermalProbeType::Thermistor_44008_44032(ThermistorParameters::from_config_word(word)?),
+            24 => ThermalProbeType::Thermistor_YSI400(ThermistorParameters::from_config_word(word)?),
+            25 => ThermalProbeType::Thermistor_Spectrum(ThermistorParameters::from_config_word(word)?),
+            28 => ThermalProbeType::Diode(DiodeParameters::from_config_word(word)),
+            29 => ThermalProbeType::SenseResistor(FixedU32::<U10>::from_bits(config_field(word, 0, 27) as u32).to_num()),
+            30 => ThermalProbeType::DirectADC(DirectADCParameters::from_config_word(word)),
+            _  => return None
+        };
+        Some(probe)
+    }
+
+    ///whether the faults of a result of this sensor mean an open or a shorted sensor
+    ///
+    ///The device always checks the wiring of RTDs and thermistors, there is nothing to enable in
+    ///the channel configuration. It reports a resistance outside of the sensor's table as a
+    ///temperature out of range: the resistance of a RTD rises with the temperature, so an open
+    ///lead reads too hot and a short too cold, for the NTC thermistors it is the other way round.
+    ///A sensor hard fault without a range fault is an open circuit. `None` for other sensor types
+    ///and for faults unrelated to the wiring.
+    pub fn wiring_fault(&self, faults: FaultFlags) -> Option<WiringFault> {
+        let (too_hot, too_cold) = if self.rtd_parameters().is_some() {
+            (WiringFault::Open, WiringFault::Shorted)
+        } else if self.thermistor_parameters().is_some() {
+            (WiringFault::Shorted, WiringFault::Open)
+        } else {
+            return None;
+        };
+        if faults.contains(FaultFlags::SENSOR_OVER_RANGE) {
+            Some(too_hot)
+        } else if faults.contains(FaultFlags::SENSOR_UNDER_RANGE) {
+            Some(too_cold)
+        } else if faults.contains(FaultFlags::SENSOR_HARD_FAULT) {
+            Some(WiringFault::Open)
+        } else {
+            None
         }
     }
 
-    pub fn start_conversion(&mut self, channel: &LTC2983Channel) -> Result<(), LTC2983Error<SPI::Error>> {
-        //start measurement
-        let mut start_command_bytes = ByteBuffer::new();
-        start_command_bytes.write_u8(LTC2983_WRITE);
-        start_command_bytes.write_u16(STATUS_REGISTER);
-        start_command_bytes.write_bits(0x4, 3);
-        start_command_bytes.write_bits(channel.identifier(), 5);
+    ///worst case time from starting a conversion of this sensor until the result is available
+    ///
+    ///Counts the ADC cycles the conversion takes following the conversion time table of the datasheet:
+    ///two cycles for most sensors, a third one for RTDs with current source rotation, thermistors
+    ///with auto ranging excitation current and diodes with three readings. A thermocouple conversion
+    ///also converts its cold junction sensor, its time has to be added. A sense resistor is not
+    ///converted on its own. The mux delay (see [`LTC2983::set_mux_delay`]) is added once per cycle
+    ///by the device and not included here.
+    pub fn conversion_time(&self, rejection: &RejectionFilter) -> Duration {
+        let cycles = match self {
+            ThermalProbeType::SenseResistor(_) => 0,
+            ThermalProbeType::Diode(param) if matches!(param.num_reading, DiodeReadingCount::READ3) => 3,
+            _ => match (self.rtd_parameters(), self.thermistor_parameters()) {
+                (Some(param), _) if param.sensor_configuration.to_bits() & 0x3 == 0x2 => 3,
+                (_, Some(param)) if matches!(param.excitation_current, ThermistorExcitationCurrent::AutoRange) => 3,
+                _ => 2
+            }
+        };
+        rejection.cycle_time() * cycles
+    }
 
-        self.spi_device.write(start_command_bytes.as_bytes())?;
+    ///name of the sensor type
+    pub fn name(&self) -> &'static str {
+        self.kind().name()
+    }
 
-        Ok(())
+    //the thermocouple parameters if the probe is a thermocouple
+    fn thermocouple_parameters(&self) -> Option<&ThermocoupleParameters> {
+        match self {
+            ThermalProbeType::Thermocouple_J(param) |
+            ThermalProbeType::Thermocouple_K(param) |
+            ThermalProbeType::Thermocouple_E(param) |
+            ThermalProbeType::Thermocouple_N(param) |
+            ThermalProbeType::Thermocouple_R(param) |
+            ThermalProbeType::Thermocouple_S(param) |
+            ThermalProbeType::Thermocouple_T(param) |
+            ThermalProbeType::Thermocouple_B(param) |
+            ThermalProbeType::Thermocouple_Custom(param) => Some(param),
+            _ => None
+        }
     }
 
-    pub fn start_multi_conversion(&mut self, channels: &Vec<LTC2983Channel>) -> Result<(), LTC2983Error<SPI::Error>> {
-        let mut write_channel_mask = ByteBuffer::new();
-        let mut mask: u32 = 0x0;
-        for chan in channels {
-            mask |= chan.mask();
+    //the probe as the device reports it back, a thermocouple with a custom table is written as custom thermocouple
+    pub(crate) fn into_written(self) -> Self {
+        match self {
+            ThermalProbeType::Thermocouple_J(param) |
+            ThermalProbeType::Thermocouple_K(param) |
+            ThermalProbeType::Thermocouple_E(param) |
+            ThermalProbeType::Thermocouple_N(param) |
+            ThermalProbeType::Thermocouple_R(param) |
+            ThermalProbeType::Thermocouple_S(param) |
+            ThermalProbeType::Thermocouple_T(param) |
+            ThermalProbeType::Thermocouple_B(param) if param.custom_table.is_some() => ThermalProbeType::Thermocouple_Custom(param),
+            probe => probe
         }
-        write_channel_mask.write_u8(LTC2983_WRITE);
-        write_channel_mask.write_u16(MULTI_CHANNEL_MASK_REGISTER);
-        write_channel_mask.write_u32(mask);
-        self.spi_device.write(write_channel_mask.as_bytes())?;
+    }
 
-        let mut start_multi_conversion_bytes = ByteBuffer::new();
-        start_multi_conversion_bytes.write_u8(LTC2983_WRITE);
-        start_multi_conversion_bytes.write_u16(STATUS_REGISTER);
-        start_multi_conversion_bytes.write_bits(0x4, 3);
-        start_multi_conversion_bytes.write_bits(0x0, 5);
+    //the input configuration of the sensors that can be measured single-ended or differential
+    fn input_configuration(&self) -> Option<&SensorConfiguration> {
+        match self {
+            ThermalProbeType::Diode(param) => Some(&param.sensor_configuration),
+            ThermalProbeType::DirectADC(param) => Some(&param.sensor_configuration),
+            _ => self.thermocouple_parameters().map(|param| &param.sensor_configuration)
+        }
+    }
 
-        self.spi_device.write(start_multi_conversion_bytes.as_bytes())?;
+    //sensors connected to the channel below can not be placed on CH1
+    fn check_channel(&self, channel: LTC2983Channel) -> Result<(), ConfigError> {
+        if channel != LTC2983Channel::CH1 {
+            return Ok(());
+        }
+        if matches!(self, ThermalProbeType::SenseResistor(_)) {
+            return Err(ConfigError::SenseResistorOnCh1);
+        }
+        if matches!(self.input_configuration(), Some(SensorConfiguration::Differential)) {
+            return Err(ConfigError::DifferentialOnCh1);
+        }
         Ok(())
     }
 
-    pub fn read_temperature(&mut self, channel: &LTC2983Channel) -> Result<LTC2983Result, LTC2983Error<SPI::Error>> {
-        let mut read_temperature_bytes = ByteBuffer::new();
-        read_temperature_bytes.write_u8(LTC2983_READ);
-        read_temperature_bytes.write_u16(channel.result_address());
-        read_temperature_bytes.write_u32(0x0); //Dummy bytes for reading
+    //diodes, RTDs and thermistors can measure the cold junction temperature of a thermocouple
+    fn can_be_cold_junction(&self) -> bool {
+        matches!(self, ThermalProbeType::Diode(_)) || self.rtd_parameters().is_some() || self.thermistor_parameters().is_some()
+    }
 
-        let mut recv: [u8; 7] = [0, 0, 0, 0, 0, 0, 0];
-        self.spi_device.transfer(&mut recv, read_temperature_bytes.as_bytes())?;
+    fn thermistor_parameters(&self) -> Option<&ThermistorParameters> {
+        match self {
+            ThermalProbeType::Thermistor_44004_44033(param) |
+            ThermalProbeType::Thermistor_44005_44030(param) |
+            ThermalProbeType::Thermistor_44007_44034(param) |
+            ThermalProbeType::Thermistor_44006_44031(param) |
+            ThermalProbeType::Thermistor_44008_44032(param) |
+            ThermalProbeType::Thermistor_YSI400(param)      |
+            ThermalProbeType::Thermistor_Spectrum(param)    => Some(param),
+            _ => None
+        }
+    }
 
-        Ok(LTC2983Result::from([recv[3], recv[4], recv[5], recv[6]]))
+    //the channel of the sense resistor used by RTDs and thermistors
+    fn r_sense_channel(&self) -> Option<LTC2983Channel> {
+        self.rtd_parameters().map(|param| param.r_sense_channel)
+            .or(self.thermistor_parameters().map(|param| param.r_sense_channel))
     }
 
-    pub fn read_multi_temperature(&mut self, channels: &Vec<LTC2983Channel>) -> Vec<Result<LTC2983Result, LTC2983Error<SPI::Error>>> {
-        channels.iter().map(|chan| {
-            self.read_temperature(chan)
-        }).collect()
+    //the RTD parameters if the probe is a RTD
+    fn rtd_parameters(&self) -> Option<&RTDParameters> {
+        match self {
+            ThermalProbeType::RTD_PT10(param)   |
+            ThermalProbeType::RTD_PT50(param)   |
+            ThermalProbeType::RTD_PT100(param)  |
+            ThermalProbeType::RTD_PT200(param)  |
+            ThermalProbeType::RTD_PT500(param)  |
+            ThermalProbeType::RTD_PT1000(param) |
+            ThermalProbeType::RTD_1000(param)   |
+            ThermalProbeType::RTD_NI120(param)  => Some(param),
+            _ => None
+        }
     }
+}
 
-    
-    ///do multiple rounds of conversion for a channel then calculate the average of the temperatures read out
-    pub fn get_temperature_avg(&mut self, channel: &LTC2983Channel, rounds: usize) -> Result<f32, LTC2983Error<SPI::Error>> {
-        let mut values = Vec::new();
+/// A temperature in degree celsius
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Celsius(pub f32);
 
-        for r in 0..rounds {
-            self.start_conversion(channel)?;
-             
-            for i in 1..3 {
+/// A voltage as measured by a direct ADC channel
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Volts(pub f32);
 
-                if !self.status().unwrap().done() {
-                    thread::sleep(Duration::from_millis(100));
-                }
-            }
+/// A resistance as measured on a sense resistor channel
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Ohms(pub f32);
 
-            if  !self.status().unwrap().done() {
-                return Err(LTC2983Error::AvgCalculationError);
-            }
-            
-            let mut was_error = false;
-            let mut v: f32 = 0.;
-            match self.read_temperature(channel) {
-                Ok(ltc_res) => {
-                    match ltc_res {
-                        LTC2983Result::Invalid(_) | LTC2983Result::Suspect(_, _) => {
-                            was_error = true;
-                        },
-                        LTC2983Result::Valid(temp) => {
-                            v = temp;
-                        }
-                    }
-                },
-                Err(_err) => {
-                    was_error = true;
-                },
-            }
+//the formatter options (e.g. the precision) apply to the number
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" °C")
+    }
+}
 
-                
-            if !was_error {
-                values.push(v);
-            } else {
-                return Err(LTC2983Error::AvgCalculationError);
-            }
-        }
+impl fmt::Display for Volts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" V")
+    }
+}
 
-        values.into_iter().reduce(|acc, e| acc + e).and_then(|v| Some(v / ( rounds as f32))).ok_or(LTC2983Error::AvgCalculationError)
+impl fmt::Display for Ohms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" Ω")
     }
+}
 
-    ///do multiple rounds of conversion for multiple channels then calculate the average of the temperatures read out
-    pub fn get_multi_temperature_avg(&mut self, channels: &Vec<LTC2983Channel>, rounds: usize) -> Result<Vec<f32>, LTC2983Error<SPI::Error>> {
-        let mut values = Vec::new();
-        let mut r = 0;
+/// Unit of a temperature
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+    Kelvin
+}
 
-        while r < rounds {
-            self.start_multi_conversion(channels)?;
-            while !self.status()?.done {}
-            let mut v = Vec::new();
-            let mut was_error = false;
-            for res in self.read_multi_temperature(channels) {
-                match res {
-                    Ok(ltc_res) => {
-                        match ltc_res {
-                            LTC2983Result::Invalid(_) | LTC2983Result::Suspect(_, _) => {
-                                was_error = true;
-                            },
-                            LTC2983Result::Valid(temp) => {
-                                v.push(temp);
-                            }
-                        }
-                    },
-                    Err(_err) => {
-                        was_error = true;
-                    },
-                }
-            }
-            if !was_error {
-                values.push(v);
-                r += 1;
+impl TemperatureUnit {
+    pub fn from_celsius(&self, celsius: f32) -> f32 {
+        match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius * 9. / 5. + 32.,
+            TemperatureUnit::Kelvin => celsius + 273.15,
+        }
+    }
+
+    pub fn to_celsius(&self, value: f32) -> f32 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Fahrenheit => (value - 32.) * 5. / 9.,
+            TemperatureUnit::Kelvin => value - 273.15,
+        }
+    }
+}
+
+/// Mains frequency rejection of the digital filter
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum RejectionFilter {
+    #[default]
+    Reject50And60Hz,
+    Reject60Hz,
+    Reject50Hz
+}
+
+impl RejectionFilter {
+    pub fn identifier(&self) -> u8 {
+        match self {
+            RejectionFilter::Reject50And60Hz => 0,
+            RejectionFilter::Reject60Hz      => 1,
+            RejectionFilter::Reject50Hz      => 2,
+        }
+    }
+
+    fn from_identifier(identifier: u8) -> Self {
+        match identifier {
+            1 => RejectionFilter::Reject60Hz,
+            2 => RejectionFilter::Reject50Hz,
+            _ => RejectionFilter::Reject50And60Hz
+        }
+    }
+
+    ///worst case duration of one conversion cycle of the ADC with this filter
+    ///
+    ///Half of the maximum conversion time of a 2 cycle conversion (t_CONV in the electrical
+    ///characteristics of the datasheet), rounded up to the next millisecond.
+    pub fn cycle_time(&self) -> Duration {
+        match self {
+            RejectionFilter::Reject50And60Hz => Duration::from_millis(84),
+            RejectionFilter::Reject60Hz      => Duration::from_millis(70),
+            RejectionFilter::Reject50Hz      => Duration::from_millis(84),
+        }
+    }
+}
+
+/// Content of the global configuration register (0x0F0)
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalConfiguration {
+    fahrenheit: bool,
+    rejection: RejectionFilter
+}
+
+impl GlobalConfiguration {
+    ///let the device report temperatures in °F instead of °C
+    pub fn fahrenheit(mut self, fahrenheit: bool) -> Self { self.fahrenheit = fahrenheit; self }
+    pub fn rejection(mut self, rejection: RejectionFilter) -> Self { self.rejection = rejection; self }
+
+    ///the unit the device reports temperatures in
+    pub fn temperature_unit(&self) -> TemperatureUnit {
+        if self.fahrenheit { TemperatureUnit::Fahrenheit } else { TemperatureUnit::Celsius }
+    }
+
+    pub fn rejection_filter(&self) -> RejectionFilter {
+        self.rejection
+    }
+
+    pub fn to_bits(&self) -> u8 {
+        // |2| temperature unit: 0 = °C, 1 = °F, |1-0| rejection filter
+        ((self.fahrenheit as u8) << 2) | self.rejection.identifier()
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Self { fahrenheit: bits & 0x4 != 0, rejection: RejectionFilter::from_identifier(bits & 0x3) }
+    }
+}
+
+/// The value held by a result register, which quantity it is depends on the configured sensor
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Reading {
+    Temperature(Celsius),
+    Voltage(Volts),
+    Resistance(Ohms)
+}
+
+impl Reading {
+    ///the plain number without its unit
+    pub fn value(&self) -> f32 {
+        match self {
+            Reading::Temperature(Celsius(value)) => *value,
+            Reading::Voltage(Volts(value)) => *value,
+            Reading::Resistance(Ohms(value)) => *value,
+        }
+    }
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reading::Temperature(temperature) => fmt::Display::fmt(temperature, f),
+            Reading::Voltage(voltage) => fmt::Display::fmt(voltage, f),
+            Reading::Resistance(resistance) => fmt::Display::fmt(resistance, f),
+        }
+    }
+}
+
+/// The fault bits of a result word (bits 31-25), bit 24 holds the valid flag.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FaultFlags(u8);
+
+impl FaultFlags {
+    pub const NONE: FaultFlags = FaultFlags(0x00);
+    pub const SENSOR_HARD_FAULT: FaultFlags = FaultFlags(0x80);
+    pub const ADC_HARD_FAULT: FaultFlags = FaultFlags(0x40);
+    pub const CJ_HARD_FAULT: FaultFlags = FaultFlags(0x20);
+    pub const CJ_SOFT_FAULT: FaultFlags = FaultFlags(0x10);
+    pub const SENSOR_OVER_RANGE: FaultFlags = FaultFlags(0x08);
+    pub const SENSOR_UNDER_RANGE: FaultFlags = FaultFlags(0x04);
+    pub const ADC_OUT_OF_RANGE: FaultFlags = FaultFlags(0x02);
+
+    ///take the fault bits from the upper byte of a result word, the valid bit is dropped
+    pub fn from_bits(bits: u8) -> Self {
+        FaultFlags(bits & 0xfe)
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: FaultFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    ///any of the hard faults (sensor, ADC, cold junction) invalidates the reading
+    pub fn hard_fault(&self) -> bool {
+        self.0 & 0xe0 != 0
+    }
+
+    ///the temperature is above the range the sensor is specified for
+    ///
+    ///The conversion itself worked, the result is reported as suspect and becomes valid again once
+    ///the temperature is back in range. This is the flag to raise an over-temperature alarm on. For
+    ///RTDs and thermistors it also shows an open or shorted sensor, see [`ThermalProbeType::wiring_fault`].
+    pub fn sensor_over_range(&self) -> bool {
+        self.contains(FaultFlags::SENSOR_OVER_RANGE)
+    }
+
+    ///the temperature is below the range the sensor is specified for, see [`FaultFlags::sensor_over_range`]
+    pub fn sensor_under_range(&self) -> bool {
+        self.contains(FaultFlags::SENSOR_UNDER_RANGE)
+    }
+
+    ///the input voltage is outside of the range the ADC can convert
+    ///
+    ///Unlike the sensor range this does not depend on the temperature: it points at the hardware or
+    ///the configuration, e.g. broken wiring, a wrong sense resistor or a too high excitation current.
+    ///The result is reported as suspect and can not be trusted.
+    pub fn adc_out_of_range(&self) -> bool {
+        self.contains(FaultFlags::ADC_OUT_OF_RANGE)
+    }
+
+    ///the faults set in `self` but not in `previous`
+    pub fn new_since(&self, previous: FaultFlags) -> FaultFlags {
+        FaultFlags(self.0 & !previous.0)
+    }
+}
+
+///lists the active faults comma separated, `no faults` if there are none
+impl fmt::Display for FaultFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [(FaultFlags, &str); 7] = [
+            (FaultFlags::SENSOR_HARD_FAULT,  "sensor hard fault"),
+            (FaultFlags::ADC_HARD_FAULT,     "ADC hard fault"),
+            (FaultFlags::CJ_HARD_FAULT,      "CJ hard fault"),
+            (FaultFlags::CJ_SOFT_FAULT,      "CJ soft fault"),
+            (FaultFlags::SENSOR_OVER_RANGE,  "sensor over range"),
+            (FaultFlags::SENSOR_UNDER_RANGE, "sensor under range"),
+            (FaultFlags::ADC_OUT_OF_RANGE,   "ADC out of range"),
+        ];
+        if self.is_empty() {
+            return f.write_str("no faults");
+        }
+        let mut active = NAMES.iter().filter(|(flag, _)| self.contains(*flag));
+        if let Some((_, name)) = active.next() {
+            f.write_str(name)?;
+        }
+        for (_, name) in active {
+            write!(f, ", {name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Broken wiring of a RTD or thermistor, see [`ThermalProbeType::wiring_fault`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WiringFault {
+    ///a broken lead, the sensor or sense resistor reads as an extremely high resistance
+    Open,
+    ///the sensor reads as (almost) no resistance
+    Shorted
+}
+
+impl fmt::Display for WiringFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WiringFault::Open    => f.write_str("sensor open"),
+            WiringFault::Shorted => f.write_str("sensor shorted"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum LTC2983Result {
+    Invalid(FaultFlags),
+    Suspect(Reading, FaultFlags),
+    Valid(Reading)
+}
+
+impl LTC2983Result {
+    //decode a result word, `reading` turns the raw 24 bit value into the quantity measured by the channel
+    fn decode(bytes: [u8; 4], reading: impl Fn(&[u8; 4]) -> Reading) -> Self {
+        let error_code = bytes[0];
+        let faults = FaultFlags::from_bits(error_code);
+        if error_code == 0x01 { // indicates valid result
+            LTC2983Result::Valid(reading(&bytes))
+        } else if faults.hard_fault() { //if any of the upper three bits of the error code are set then the result is invalid
+            LTC2983Result::Invalid(faults)
+        } else { // in all other cases the reading should regarded as suspect
+            LTC2983Result::Suspect(reading(&bytes), faults)
+        }
+    }
+
+    ///the faults reported with the result
+    pub fn faults(&self) -> FaultFlags {
+        match self {
+            LTC2983Result::Invalid(faults) | LTC2983Result::Suspect(_, faults) => *faults,
+            LTC2983Result::Valid(_) => FaultFlags::NONE,
+        }
+    }
+
+    ///the temperature in °C of a valid or suspect result, `None` for invalid results and channels not measuring a temperature
+    pub fn temperature(&self) -> Option<f32> {
+        match self {
+            LTC2983Result::Valid(Reading::Temperature(Celsius(celsius))) |
+            LTC2983Result::Suspect(Reading::Temperature(Celsius(celsius)), _) => Some(*celsius),
+            _ => None
+        }
+    }
+
+    ///the value of a valid result in the unit of its [`Reading`], the faults of suspect and invalid results as error
+    pub fn into_result(self) -> Result<f32, FaultFlags> {
+        match self {
+            LTC2983Result::Valid(reading) => Ok(reading.value()),
+            LTC2983Result::Suspect(_, faults) | LTC2983Result::Invalid(faults) => Err(faults)
+        }
+    }
+
+    //decode a result word of a direct ADC channel
+    fn from_voltage_bytes(bytes: [u8; 4]) -> Self {
+        Self::decode(bytes, |bytes| {
+            let value = FixedI32::<U21>::from_be_bytes(reformat_fixedf24_to_fixed_f32(bytes[1..=3].try_into().unwrap()));
+            Reading::Voltage(Volts(value.to_num()))
+        })
+    }
+}
+
+///e.g. `25.1 °C`, `23.1 °C (suspect: CJ soft fault)` or `INVALID (sensor hard fault)`
+impl fmt::Display for LTC2983Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LTC2983Result::Valid(reading) => fmt::Display::fmt(reading, f),
+            LTC2983Result::Suspect(reading, faults) => {
+                fmt::Display::fmt(reading, f)?;
+                write!(f, " (suspect: {faults})")
             }
+            LTC2983Result::Invalid(faults) => write!(f, "INVALID ({faults})"),
         }
+    }
+}
 
-        values.into_iter().reduce(|acc, e| {
-            acc.iter().zip(e.iter()).map(|(&a, &b)| a+b).collect::<Vec<f32>>() // do a component wise add of the values
-        }).and_then(|v| {
-            Some(v.iter().map(|x| x/(rounds as f32)).collect()) // calculate average by dividing by the amount of values captured
-        }).ok_or(LTC2983Error::AvgCalculationError)
+impl From<[u8; 4]> for LTC2983Result {
+    fn from(bytes: [u8; 4]) -> Self {
+        Self::decode(bytes, |bytes| Reading::Temperature(Celsius(fixed_from_result_bytes(bytes).to_num())))
     }
 }
 
-fn reformat_fixedf24_to_fixed_f32(bytes_f24: &[u8; 3]) -> [u8; 4]{
-    if bytes_f24[0] & 0x80 == 0x80 {
-        [0xff, bytes_f24[0], bytes_f24[1], bytes_f24[2]]
-    } else {
-        [0x00, bytes_f24[0], bytes_f24[1], bytes_f24[2]]
+/// Voltage across a sense resistor, see [`LTC2983::read_sense_voltage`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SenseVoltage {
+    ///drop calculated from the configured resistance and excitation current
+    pub expected: f32,
+    ///drop calculated from the resistance reported by the device, if it reports one
+    pub measured: Option<f32>
+}
+
+/// Result of a thermocouple together with the cold junction temperature used to compensate it,
+/// see [`LTC2983::read_with_cold_junction`]
+#[derive(Debug, Clone)]
+pub struct ColdJunctionReading {
+    pub thermocouple: LTC2983Result,
+    pub cold_junction: LTC2983Result
+}
+
+/// Average of multiple conversions, see [`LTC2983::get_temperature_avg_detailed`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemperatureAverage {
+    pub average: f32,
+    ///number of readings the average was calculated from
+    pub valid_samples: usize,
+    ///number of suspect or invalid readings left out
+    pub rejected: usize
+}
+
+/// Statistics over the valid readings of multiple conversions, see [`LTC2983::get_temperature_stats`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemperatureStats {
+    pub mean: f32,
+    pub median: f32,
+    pub min: f32,
+    pub max: f32,
+    ///sample standard deviation, 0 for a single sample
+    pub std_dev: f32,
+    pub valid_samples: usize,
+    pub rejected: usize
+}
+
+impl TemperatureStats {
+    fn from_samples(samples: &Samples) -> Option<Self> {
+        let mean = mean(&samples.values)?;
+        let mut sorted = samples.values.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) { (sorted[n / 2 - 1] + sorted[n / 2]) / 2. } else { sorted[n / 2] };
+        let std_dev = if n > 1 {
+            (sorted.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / (n - 1) as f32).sqrt()
+        } else {
+            0.
+        };
+
+        Some(TemperatureStats {
+            mean,
+            median,
+            min: sorted[0],
+            max: sorted[n - 1],
+            std_dev,
+            valid_samples: n,
+            rejected: samples.rejected
+        })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use fixed::{FixedI32, types::extra::U10};
+//valid readings of a channel collected over multiple conversions
+struct Samples {
+    values: Vec<f32>,
+    rejected: usize
+}
 
-    use super::*;
+/// Extremes of the valid temperatures read from a channel, see [`LTC2983::channel_stats`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChannelStats {
+    pub min: f32,
+    pub max: f32,
+    ///number of valid temperatures read
+    pub count: usize
+}
 
-    #[test]
-    fn test_fixedf24_u10_to_f32_signed() {
-        let bytes: [u8; 3] = [ 0x7f, 0xff, 0xff ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (8191.999 as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+impl ChannelStats {
+    fn update(stats: Option<Self>, value: f32) -> Self {
+        match stats {
+            None => ChannelStats { min: value, max: value, count: 1 },
+            Some(stats) => ChannelStats { min: stats.min.min(value), max: stats.max.max(value), count: stats.count + 1 }
+        }
+    }
+}
 
-        let bytes: [u8; 3] = [ 0x10, 0x00, 0x00 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (1024 as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+/// The configuration registers read back from the device, see [`LTC2983::snapshot`]
+///
+/// The raw register contents are kept, so a snapshot shows exactly what is programmed. Channel
+/// configuration words can be decoded with [`ThermalProbeType::from_config_word`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceSnapshot {
+    ///global configuration register (0x0F0)
+    pub global_configuration: u8,
+    ///multiple channel mask register (0x0F4-0x0F7)
+    pub multi_channel_mask: u32,
+    ///mux configuration delay register (0x0FF), in steps of 100µs
+    pub mux_delay: u8,
+    ///channel configuration registers of CH1 to CH20 (0x200-0x24F)
+    pub channel_config: [u32; 20]
+}
 
-        let bytes: [u8; 3] = [ 0x00, 0x04, 0x00 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (1 as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+/// Temperature unit, rejection filter and mux delay read back from the device, see [`LTC2983::read_global_settings`]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlobalSettings {
+    pub temperature_unit: TemperatureUnit,
+    pub rejection_filter: RejectionFilter,
+    ///delay after switching the input mux in µs, a multiple of 100µs, see [`LTC2983::set_mux_delay`]
+    pub mux_delay_us: u32
+}
 
-        let bytes: [u8; 3] = [ 0x00, 0x00, 0x01 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (1./1024. as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+/// The result of a channel read by [`LTC2983::read_multi_temperature`]
+pub type ChannelResult<E> = (LTC2983Channel, Result<LTC2983Result, LTC2983Error<E>>);
 
-        let bytes: [u8; 3] = [ 0x00, 0x00, 0x00 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (0. as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+/// Result of [`LTC2983::measure`]
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub channel: LTC2983Channel,
+    pub result: LTC2983Result,
+    ///time of the driver's [`Clock`] when the finished conversion was observed
+    pub timestamp: Duration
+}
 
-        let bytes: [u8; 3] = [ 0xff, 0xff, 0xff ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (-1./1024. as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+/// Position of a reading relative to the limits of a [`ThresholdMonitor`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ThresholdLevel {
+    Below,
+    Within,
+    Above
+}
 
-        let bytes: [u8; 3] = [ 0xff, 0xfc, 0x00 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (-1 as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+/// Compares the readings of a channel against a low and a high limit
+///
+/// With a hysteresis band a reading has to come back by the hysteresis into the limits before
+/// the level returns to [`ThresholdLevel::Within`], so readings around a limit do not chatter.
+#[derive(Debug, Clone)]
+pub struct ThresholdMonitor {
+    channel: LTC2983Channel,
+    low: f32,
+    high: f32,
+    hysteresis: f32,
+    level: ThresholdLevel
+}
 
-        let bytes: [u8; 3] = [ 0xfb, 0xbb, 0x67 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (-273.15 as f32) < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+impl ThresholdMonitor {
+    ///monitor `channel` against `low` and `high` without hysteresis, starting out `Within`
+    pub fn new(channel: LTC2983Channel, low: f32, high: f32) -> Self {
+        Self { channel, low, high, hysteresis: 0., level: ThresholdLevel::Within }
+    }
 
-        let bytes: [u8; 3] = [ 0xf8, 0xd1, 0x52 ];
-        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
-        assert!(value.to_num::<f32>() - (-459.67 as f32) < 1./1027.); // error should be smaller than smallest fixed point value 1./1024.
+    pub fn hysteresis(mut self, hysteresis: f32) -> Self { self.hysteresis = hysteresis; self }
+
+    ///the level of the last classified reading
+    pub fn level(&self) -> ThresholdLevel {
+        self.level
+    }
+
+    ///classify a reading and remember the level for the hysteresis of the next one
+    pub fn classify(&mut self, value: f32) -> ThresholdLevel {
+        self.level = match self.level {
+            ThresholdLevel::Above if value > self.high - self.hysteresis => ThresholdLevel::Above,
+            ThresholdLevel::Below if value < self.low + self.hysteresis => ThresholdLevel::Below,
+            _ if value > self.high => ThresholdLevel::Above,
+            _ if value < self.low => ThresholdLevel::Below,
+            _ => ThresholdLevel::Within,
+        };
+        self.level
+    }
+
+    ///convert the channel and classify the result
+    ///
+    ///Only valid readings are classified, for suspect and invalid results the level is `None` and
+    ///the remembered level stays unchanged.
+    pub fn measure<SPI, W, P>(&mut self, ltc: &mut LTC2983<SPI, W, P>) -> Result<(Option<ThresholdLevel>, LTC2983Result), LTC2983Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        W: ConversionWaiter
+    {
+        let result = ltc.measure(self.channel)?.result;
+        let level = match &result {
+            LTC2983Result::Valid(reading) => Some(self.classify(reading.value())),
+            _ => None
+        };
+        Ok((level, result))
+    }
+}
+
+/// Outcome of a single channel of [`LTC2983::scan_all_with_deadline`]
+#[derive(Debug, Clone)]
+pub enum ScanOutcome {
+    Completed(LTC2983Result),
+    Timeout
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LTC2983Channel {
+    CH1,
+    CH2,
+    CH3,
+    CH4,
+    CH5,
+    CH6,
+    CH7,
+    CH8,
+    CH9,
+    CH10,
+    CH11,
+    CH12,
+    CH13,
+    CH14,
+    CH15,
+    CH16,
+    CH17,
+    CH18,
+    CH19,
+    CH20
+}
+
+impl LTC2983Channel {
+    ///all channels in ascending order
+    pub const ALL: [LTC2983Channel; 20] = [
+        LTC2983Channel::CH1,  LTC2983Channel::CH2,  LTC2983Channel::CH3,  LTC2983Channel::CH4,
+        LTC2983Channel::CH5,  LTC2983Channel::CH6,  LTC2983Channel::CH7,  LTC2983Channel::CH8,
+        LTC2983Channel::CH9,  LTC2983Channel::CH10, LTC2983Channel::CH11, LTC2983Channel::CH12,
+        LTC2983Channel::CH13, LTC2983Channel::CH14, LTC2983Channel::CH15, LTC2983Channel::CH16,
+        LTC2983Channel::CH17, LTC2983Channel::CH18, LTC2983Channel::CH19, LTC2983Channel::CH20,
+    ];
+
+    ///iterate over all channels from CH1 to CH20
+    pub fn all() -> impl Iterator<Item = LTC2983Channel> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn start_address(&self) -> u16 {
+        match self {
+            LTC2983Channel::CH1  => 0x200,
+            LTC2983Channel::CH2  => 0x204,
+            LTC2983Channel::CH3  => 0x208,
+            LTC2983Channel::CH4  => 0x20C,
+            LTC2983Channel::CH5  => 0x210,
+            LTC2983Channel::CH6  => 0x214,
+            LTC2983Channel::CH7  => 0x218,
+            LTC2983Channel::CH8  => 0x21C,
+            LTC2983Channel::CH9  => 0x220,
+            LTC2983Channel::CH10 => 0x224,
+            LTC2983Channel::CH11 => 0x228,
+            LTC2983Channel::CH12 => 0x22C,
+            LTC2983Channel::CH13 => 0x230,
+            LTC2983Channel::CH14 => 0x234,
+            LTC2983Channel::CH15 => 0x238,
+            LTC2983Channel::CH16 => 0x23C,
+            LTC2983Channel::CH17 => 0x240,
+            LTC2983Channel::CH18 => 0x244,
+            LTC2983Channel::CH19 => 0x248,
+            LTC2983Channel::CH20 => 0x24C
+        }
+    }
+
+    pub fn result_address(&self) -> u16 {
+        match self {
+            LTC2983Channel::CH1  => 0x010,
+            LTC2983Channel::CH2  => 0x014,
+            LTC2983Channel::CH3  => 0x018,
+            LTC2983Channel::CH4  => 0x01C,
+            LTC2983Channel::CH5  => 0x020,
+            LTC2983Channel::CH6  => 0x024,
+            LTC2983Channel::CH7  => 0x028,
+            LTC2983Channel::CH8  => 0x02C,
+            LTC2983Channel::CH9  => 0x030,
+            LTC2983Channel::CH10 => 0x034,
+            LTC2983Channel::CH11 => 0x038,
+            LTC2983Channel::CH12 => 0x03C,
+            LTC2983Channel::CH13 => 0x040,
+            LTC2983Channel::CH14 => 0x044,
+            LTC2983Channel::CH15 => 0x048,
+            LTC2983Channel::CH16 => 0x04C,
+            LTC2983Channel::CH17 => 0x050,
+            LTC2983Channel::CH18 => 0x054,
+            LTC2983Channel::CH19 => 0x058,
+            LTC2983Channel::CH20 => 0x05C,
+        }
+    }
+
+    pub fn identifier(&self) -> u64 {
+        match self {
+            LTC2983Channel::CH1  => 1,
+            LTC2983Channel::CH2  => 2,
+            LTC2983Channel::CH3  => 3,
+            LTC2983Channel::CH4  => 4,
+            LTC2983Channel::CH5  => 5,
+            LTC2983Channel::CH6  => 6,
+            LTC2983Channel::CH7  => 7,
+            LTC2983Channel::CH8  => 8,
+            LTC2983Channel::CH9  => 9,
+            LTC2983Channel::CH10 => 10,
+            LTC2983Channel::CH11 => 11,
+            LTC2983Channel::CH12 => 12,
+            LTC2983Channel::CH13 => 13,
+            LTC2983Channel::CH14 => 14,
+            LTC2983Channel::CH15 => 15,
+            LTC2983Channel::CH16 => 16,
+            LTC2983Channel::CH17 => 17,
+            LTC2983Channel::CH18 => 18,
+            LTC2983Channel::CH19 => 19,
+            LTC2983Channel::CH20 => 20,
+        }
+    }
+
+    pub fn mask(&self) -> u32 {
+       0x1 << (self.identifier() - 1)
+    }
+
+    //position of the channel in per channel arrays
+    fn index(&self) -> usize {
+        self.identifier() as usize - 1
+    }
+
+    ///the channel with the number `identifier` as used by the device, `None` for 0 and numbers above 20
+    pub fn from_identifier(identifier: u64) -> Option<LTC2983Channel> {
+        match identifier {
+            1..=20 => Some(Self::ALL[identifier as usize - 1]),
+            _ => None
+        }
+    }
+}
+
+/// A channel number reported by the device that does not belong to a channel
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Error, Copy, Clone, PartialEq, Eq)]
+#[error("{0} is not a channel number, the channels are numbered 1 to 20!")]
+pub struct InvalidChannel(pub u8);
+
+impl TryFrom<u8> for LTC2983Channel {
+    type Error = InvalidChannel;
+
+    fn try_from(identifier: u8) -> Result<Self, Self::Error> {
+        LTC2983Channel::from_identifier(identifier.into()).ok_or(InvalidChannel(identifier))
+    }
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct LTC2983Status {
+    start: bool,
+    done: bool,
+    //1 bit unused
+    channel_selection: u8
+}
+
+impl LTC2983Status {
+    pub fn done(&self) -> bool {
+        self.done
+    }
+
+    ///true while a conversion is running
+    pub fn started(&self) -> bool {
+        self.start
+    }
+
+    ///the channel selected by the last conversion command, `None` for a multi channel conversion
+    pub fn active_channel(&self) -> Option<LTC2983Channel> {
+        LTC2983Channel::from_identifier(self.channel_selection as u64)
+    }
+}
+
+impl From<u8> for LTC2983Status {
+    fn from(data: u8) -> Self {
+        LTC2983Status {
+            start: data & 0x80 == 0x80,
+            done: data & 0x40 == 0x40,
+            channel_selection: data & 0x1f
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum LTC2983OcCurrent {
+    External,
+    #[default]
+    I10uA,
+    I100uA,
+    I500uA,
+    I1mA
+}
+
+impl LTC2983OcCurrent {
+    pub fn identifier(&self) -> u64 {
+        match self {
+            LTC2983OcCurrent::External => 0,
+            LTC2983OcCurrent::I10uA => 4,
+            LTC2983OcCurrent::I100uA => 5,
+            LTC2983OcCurrent::I500uA => 6,
+            LTC2983OcCurrent::I1mA => 7,
+        }
+    }
+
+    fn from_identifier(identifier: u64) -> Option<Self> {
+        match identifier {
+            0 => Some(LTC2983OcCurrent::External),
+            4 => Some(LTC2983OcCurrent::I10uA),
+            5 => Some(LTC2983OcCurrent::I100uA),
+            6 => Some(LTC2983OcCurrent::I500uA),
+            7 => Some(LTC2983OcCurrent::I1mA),
+            _ => None
+        }
+    }
+}
+
+/// A sensor configuration that can not be encoded into a channel configuration word or refers to unsuitable channels
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ConfigError {
+    #[error("Current source rotation needs a 4-wire RTD excited by the internal current source!")]
+    UnsupportedRotation,
+    #[error("Current source rotation needs a differential thermistor!")]
+    UnsupportedThermistorRotation,
+    #[error("Current source rotation needs a fixed thermistor excitation current, auto ranging is not supported!")]
+    AutoRangeWithRotation,
+    #[error("Only platinum RTDs (PT-10 to PT-1000) support a curve other than the European standard!")]
+    InvalidRTDCurve,
+    #[error("Sense resistance {0} Ohm is outside of 0 to 131072 Ohm!")]
+    SenseResistanceOutOfRange(f32),
+    #[error("Diode ideality factor {0} is outside of 0 to 4!")]
+    IdealityFactorOutOfRange(f32),
+    #[error("Custom table address {0:#05x} is outside of 0x250-0x34C, the start addresses the data pointer can hold!")]
+    CustomAddressOutOfRange(u16),
+    #[error("Custom table address {0:#05x} is not a multiple of 4!")]
+    CustomAddressUnaligned(u16),
+    #[error("Custom table of {entries} entries at {address:#05x} does not fit 1 to 64 entries ending at 0x3CF!")]
+    CustomTableTooLong { address: u16, entries: u8 },
+    #[error("Cold junction channel {0:?} is not configured as diode, RTD or thermistor!")]
+    ColdJunctionUnconfigured(LTC2983Channel),
+    #[error("Channel {0:?} is not configured as sense resistor!")]
+    SenseResistorUnconfigured(LTC2983Channel),
+    #[error("A sense resistor is connected to the channel below, it can not be placed on CH1!")]
+    SenseResistorOnCh1,
+    #[error("A differential sensor is measured against the channel below, it can not be placed on CH1!")]
+    DifferentialOnCh1
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Error)]
+pub enum LTC2983Error<SPI> {
+    #[error("SPI communication error: {0:?}")]
+    SpiError(#[from] SPI),
+    #[error("Channel {0:?} not configured!")]
+    ChannelUnconfigured(LTC2983Channel),
+    #[error("Error while calculating average from mutliple rounds of readouts.")]
+    AvgCalculationError,
+    #[error("Timed out while waiting for the conversion to finish.")]
+    ConversionTimeout,
+    #[error("No conversion was started.")]
+    NoConversionRunning,
+    #[error("No RTD or thermistor is using the sense resistor on channel {0:?}!")]
+    SenseResistorUnused(LTC2983Channel),
+    #[error("Channel {0:?} does not measure a temperature!")]
+    NotATemperature(LTC2983Channel),
+    #[error("Result is not valid, faults: {0:?}")]
+    FaultyResult(FaultFlags),
+    #[error("Only {0} samples left, at least 3 are needed.")]
+    TooFewSamples(usize),
+    #[error("{0} of {1} readings were rejected as invalid or suspect.")]
+    SamplesRejected(usize, usize),
+    #[error("No valid reading to calculate the average from.")]
+    NoValidSamples,
+    #[error("A conversion is already in progress.")]
+    ConversionInProgress,
+    #[error("Channel {0:?} is assigned more than once!")]
+    DuplicateChannel(LTC2983Channel),
+    #[error("Access of {1} bytes at {0:#05x} is outside of the register space 0x000-0x3CF.")]
+    RegisterOutOfRange(u16, usize),
+    #[error("Channel {0:?} is not a thermocouple with a cold junction channel!")]
+    NoColdJunction(LTC2983Channel),
+    #[error("EEPROM operation failed, EEPROM status: {0:#04x}")]
+    EepromFailed(u8),
+    #[error("No channel is configured.")]
+    NoChannelsConfigured,
+    #[error("No conversion of channel {0:?} was started yet.")]
+    NoConversionYet(LTC2983Channel),
+    #[error("Could not drive or read the RESET or INTERRUPT pin.")]
+    PinFault,
+    #[error("Invalid sensor configuration: {0}")]
+    InvalidConfig(ConfigError),
+    #[error("The configuration can not be changed while a conversion is running.")]
+    DeviceBusy,
+    #[error("The device did not become ready in time.")]
+    DeviceNotReady,
+    #[error("The channel is configured as {actual:?}, the read is meant for {expected:?}!")]
+    SensorKindMismatch { expected: SensorKind, actual: SensorKind },
+    #[error("The result of channel {0:?} could not be confirmed to belong to the latest conversion!")]
+    StaleResult(LTC2983Channel),
+    #[error("Channel {channel:?} reads back configuration {actual:#010x} instead of {expected:#010x}!")]
+    WriteVerifyFailed { channel: LTC2983Channel, expected: u32, actual: u32 }
+}
+
+/// What to do when a conversion is started while the device is still busy converting.
+///
+/// The device does not queue conversion commands, a command written during a running conversion
+/// leads to undefined results. Channel configurations are never written during a conversion:
+/// with `Queue` the conversion is waited for, otherwise [`LTC2983Error::DeviceBusy`] is returned.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum BusyPolicy {
+    ///write the command without checking the device state
+    #[default]
+    Interrupt,
+    ///return [`LTC2983Error::ConversionInProgress`]
+    Reject,
+    ///wait for the running conversion to finish before starting the new one
+    Queue
+}
+
+/// How [`LTC2983::read_temperature`] reports results the device marks as suspect
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum SuspectPolicy {
+    ///report them as [`LTC2983Result::Suspect`]
+    #[default]
+    Keep,
+    ///report them as [`LTC2983Result::Valid`]
+    Accept,
+    ///report them as [`LTC2983Result::Invalid`]
+    Reject,
+    ///report them as valid if all of their faults are part of the given flags, otherwise as invalid
+    AcceptIfOnly(FaultFlags)
+}
+
+impl SuspectPolicy {
+    fn apply(&self, result: LTC2983Result) -> LTC2983Result {
+        let LTC2983Result::Suspect(reading, faults) = result else {
+            return result;
+        };
+        let accept = match self {
+            SuspectPolicy::Keep => return LTC2983Result::Suspect(reading, faults),
+            SuspectPolicy::Accept => true,
+            SuspectPolicy::Reject => false,
+            SuspectPolicy::AcceptIfOnly(flags) => faults.bits() & !flags.bits() == 0
+        };
+        if accept { LTC2983Result::Valid(reading) } else { LTC2983Result::Invalid(faults) }
+    }
+}
+
+/// Tracks the conversion the driver started last.
+///
+/// `Idle` -> [`LTC2983::start_conversion`] -> `Converting` -> [`LTC2983::poll_done`] -> `Done`
+/// -> [`LTC2983::read_temperature`] -> `Idle`
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum ConversionState {
+    #[default]
+    Idle,
+    Converting,
+    Done
+}
+
+/// Strategy used to wait for a started conversion to finish.
+///
+/// The waiter is handed a closure that reads the done bit of the command status register. It
+/// returns `Ok(true)` once the conversion finished and `Ok(false)` if it gave up waiting.
+pub trait ConversionWaiter {
+    fn wait<E>(&mut self, done: &mut dyn FnMut() -> Result<bool, E>) -> Result<bool, E>;
+}
+
+/// Busy-poll the status register until the conversion is done or `max_polls` reads were made.
+#[derive(Debug)]
+pub struct PollWaiter {
+    max_polls: usize
+}
+
+impl Default for PollWaiter {
+    fn default() -> Self {
+        Self { max_polls: 100_000 }
+    }
+}
+
+impl PollWaiter {
+    pub fn new(max_polls: usize) -> Self {
+        Self { max_polls }
+    }
+}
+
+impl ConversionWaiter for PollWaiter {
+    fn wait<E>(&mut self, done: &mut dyn FnMut() -> Result<bool, E>) -> Result<bool, E> {
+        for _ in 0..self.max_polls {
+            if done()? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Poll the status register and sleep `interval_us` between the reads, gives up after `timeout_us`.
+///
+/// A zero interval polls back to back, every poll then counts as 1µs towards the timeout.
+#[derive(Debug)]
+pub struct DelayWaiter<D> {
+    delay: D,
+    interval_us: u32,
+    timeout_us: u32
+}
+
+impl<D> DelayWaiter<D> where D: DelayNs {
+    pub fn new(delay: D, interval_us: u32, timeout_us: u32) -> Self {
+        Self { delay, interval_us, timeout_us }
+    }
+}
+
+impl<D> ConversionWaiter for DelayWaiter<D> where D: DelayNs {
+    fn wait<E>(&mut self, done: &mut dyn FnMut() -> Result<bool, E>) -> Result<bool, E> {
+        let mut waited: u32 = 0;
+        loop {
+            if done()? {
+                return Ok(true);
+            }
+            if waited >= self.timeout_us {
+                return Ok(false);
+            }
+            self.delay.delay_us(self.interval_us);
+            waited = waited.saturating_add(self.interval_us.max(1));
+        }
+    }
+}
+
+/// Wait for the INTERRUPT pin of the device to go high, which signals the end of a conversion.
+///
+/// The pin is sampled every `interval_us` until `timeout_us` passed, the status register is only
+/// read once the pin is high to confirm the conversion is done. A pin that can not be read is
+/// treated as low. As for [`DelayWaiter`] a zero interval counts every sample as 1µs.
+#[derive(Debug)]
+pub struct InterruptWaiter<P, D> {
+    pin: P,
+    delay: D,
+    interval_us: u32,
+    timeout_us: u32
+}
+
+impl<P, D> InterruptWaiter<P, D> where P: InputPin, D: DelayNs {
+    pub fn new(pin: P, delay: D, interval_us: u32, timeout_us: u32) -> Self {
+        Self { pin, delay, interval_us, timeout_us }
+    }
+}
+
+impl<P, D> ConversionWaiter for InterruptWaiter<P, D> where P: InputPin, D: DelayNs {
+    fn wait<E>(&mut self, done: &mut dyn FnMut() -> Result<bool, E>) -> Result<bool, E> {
+        let mut waited: u32 = 0;
+        loop {
+            if self.pin.is_high().unwrap_or(false) && done()? {
+                return Ok(true);
+            }
+            if waited >= self.timeout_us {
+                return Ok(false);
+            }
+            self.delay.delay_us(self.interval_us);
+            waited = waited.saturating_add(self.interval_us.max(1));
+        }
+    }
+}
+
+/// Monotonic time source for the timestamps and deadlines of the driver, see [`LTC2983::set_clock`]
+///
+/// The time is counted from an arbitrary but fixed point and never goes backwards, a tick counter
+/// of a hardware timer is enough on targets without `std::time`.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+/// [`Clock`] using [`std::time::Instant`], counting from the creation of the clock
+#[derive(Debug, Clone, Copy)]
+pub struct StdClock {
+    epoch: Instant
+}
+
+impl Default for StdClock {
+    fn default() -> Self {
+        Self { epoch: Instant::now() }
+    }
+}
+
+impl Clock for StdClock {
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+}
+
+/// Placeholder for a driver without the RESET and INTERRUPT pins wired
+#[derive(Debug, Default)]
+pub struct NoPins;
+
+/// The RESET and INTERRUPT pins of the device, see [`LTC2983::with_pins`]
+///
+/// The delay is used to time the reset pulse and to sample the interrupt pin.
+#[derive(Debug)]
+pub struct ControlPins<RST, INT, D> {
+    reset: RST,
+    interrupt: INT,
+    delay: D
+}
+
+impl<RST, INT, D> ControlPins<RST, INT, D> where RST: OutputPin, INT: InputPin, D: DelayNs {
+    pub fn new(reset: RST, interrupt: INT, delay: D) -> Self {
+        Self { reset, interrupt, delay }
+    }
+}
+
+/// The sensors of a whole board, e.g. loaded from a configuration file
+///
+/// Serialized as a map from the channel name to the sensor:
+///
+///``` json
+/// { "CH2": { "SenseResistor": 2000.0 }, "CH4": { "RTD_PT100": { "r_sense_channel": "CH2" } } }
+///```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChannelMap(BTreeMap<LTC2983Channel, ThermalProbeType>);
+
+impl ChannelMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn channel(mut self, channel: LTC2983Channel, probe: ThermalProbeType) -> Self {
+        self.0.insert(channel, probe);
+        self
+    }
+
+    pub fn get(&self, channel: LTC2983Channel) -> Option<&ThermalProbeType> {
+        self.0.get(&channel)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (LTC2983Channel, &ThermalProbeType)> {
+        self.0.iter().map(|(channel, probe)| (*channel, probe))
+    }
+}
+
+///check all channels of `map` before anything is written and report every problem found
+///
+///Each channel has to be encodable (see [`ThermalProbeType::to_config_word`]), its cold junction and
+///sense resistor channels have to be part of the map, sense resistors and differential thermocouples,
+///diodes and direct ADC inputs can not be placed on CH1. Every error comes with the channel of the
+///map it was found on, the errors are ordered by channel.
+pub fn validate_config(map: &ChannelMap) -> Result<(), Vec<(LTC2983Channel, ConfigError)>> {
+    let mut errors = Vec::new();
+    for (channel, probe) in map.iter() {
+        if let Err(err) = probe.to_config_word() {
+            errors.push((channel, err));
+        }
+        if let Err(err) = validate_references(probe, |channel| map.get(channel)) {
+            errors.push((channel, err));
+        }
+        if let Err(err) = probe.check_channel(channel) {
+            errors.push((channel, err));
+        }
+    }
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// Collects the configuration of several channels and writes them in as few SPI transactions as possible.
+///
+/// The configuration registers of the channels are contiguous from 0x200, so neighbouring channels
+/// are written in a single burst.
+#[derive(Debug, Clone, Default)]
+pub struct ChannelConfigBuilder {
+    entries: Vec<(LTC2983Channel, ThermalProbeType)>
+}
+
+impl ChannelConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn channel(mut self, channel: LTC2983Channel, probe: ThermalProbeType) -> Self {
+        self.entries.push((channel, probe));
+        self
+    }
+
+    ///write all collected channel configurations
+    ///
+    ///If a channel was assigned more than once [`LTC2983Error::DuplicateChannel`] is returned before anything is written.
+    pub fn commit<SPI, W, P>(self, ltc: &mut LTC2983<SPI, W, P>) -> Result<(), LTC2983Error<SPI::Error>>
+    where
+        SPI: SpiDevice,
+        W: ConversionWaiter
+    {
+        let mut entries = self.entries;
+        entries.sort_by_key(|(channel, _)| channel.index());
+        if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(LTC2983Error::DuplicateChannel(pair[0].0));
+        }
+        //channels can refer to other channels of the same commit as well as to already configured ones
+        let configured = |channel: LTC2983Channel| {
+            entries.iter()
+                .find(|(entry, _)| *entry == channel)
+                .map(|(_, probe)| probe)
+                .or(ltc.channels[channel.index()].as_ref())
+        };
+        for (channel, probe) in &entries {
+            probe.check_channel(*channel).map_err(LTC2983Error::InvalidConfig)?;
+            validate_references(probe, configured).map_err(LTC2983Error::InvalidConfig)?;
+        }
+
+        let writes = offline::channel_config_writes(&entries).map_err(LTC2983Error::InvalidConfig)?;
+        ltc.ensure_not_converting()?;
+        let mut entries = entries.into_iter();
+        for (address, bytes) in writes {
+            ltc.write_register(address, &bytes)?;
+            //every channel configuration register holds 4 bytes
+            for (channel, probe) in entries.by_ref().take(bytes.len() / 4) {
+                ltc.channels[channel.index()] = Some(probe.into_written());
+            }
+        }
+        Ok(())
+    }
+}
+
+//how often failed SPI transfers are repeated, see `LTC2983::set_retry_policy`
+#[derive(Default)]
+struct RetryPolicy {
+    retries: usize,
+    backoff: Option<Box<dyn FnMut(usize) + Send>>
+}
+
+impl RetryPolicy {
+    fn run<T, E>(&mut self, mut transfer: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+        let mut attempt = 0;
+        loop {
+            match transfer() {
+                Err(_) if attempt < self.retries => {
+                    attempt += 1;
+                    #[cfg(feature = "log")]
+                    log::debug!("SPI transfer failed, retry {attempt} of {}", self.retries);
+                    if let Some(backoff) = self.backoff.as_mut() {
+                        backoff(attempt);
+                    }
+                }
+                result => return result
+            }
+        }
+    }
+}
+
+/// A channel that was set up with [`LTC2983::setup_channel`]
+///
+/// The handle can only be obtained from a successful setup, reading through it with
+/// [`LTC2983::read_configured`] instead of [`LTC2983::read_temperature`] turns reading an
+/// unconfigured channel into a compile error.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConfiguredChannel(LTC2983Channel);
+
+impl ConfiguredChannel {
+    pub fn channel(&self) -> LTC2983Channel {
+        self.0
+    }
+}
+
+//software correction of the temperatures of a channel, see LTC2983::set_linearization
+type Linearization = Box<dyn Fn(f32) -> f32 + Send>;
+
+pub struct LTC2983<SPI, W = PollWaiter, P = NoPins> {
+    spi_device: SPI,
+    waiter: W,
+    pins: P,
+    state: ConversionState,
+    channels: [Option<ThermalProbeType>; 20],
+    faults: [FaultFlags; 20],
+    stats: [Option<ChannelStats>; 20],
+    fault_callback: Option<Box<dyn FnMut(LTC2983Channel, FaultFlags) + Send>>,
+    clock: Box<dyn Clock + Send>,
+    linearizations: [Option<Linearization>; 20],
+    global_configuration: GlobalConfiguration,
+    busy_policy: BusyPolicy,
+    suspect_policy: SuspectPolicy,
+    multi_conversion_mask: u32,
+    //channels a conversion was started for by this driver
+    converted: u32,
+    //channels converted by this driver whose results were not read since
+    unread: u32,
+    check_converted: bool,
+    verify_writes: bool,
+    retry: RetryPolicy,
+}
+
+impl<SPI> LTC2983<SPI> where SPI: SpiDevice {
+    pub fn new(spi_device: SPI) -> Self {
+        LTC2983::with_waiter(spi_device, PollWaiter::default())
+    }
+}
+
+impl<BUS, CS> LTC2983<bus::BusDevice<BUS, CS>> where BUS: SpiBus, CS: OutputPin {
+    ///create the driver for a bus that is not shared, with a manually driven chip select pin
+    ///
+    ///Chip select is held low for each complete frame, see [`bus::BusDevice`].
+    pub fn with_bus(bus: BUS, cs: CS) -> Result<Self, CS::Error> {
+        Ok(LTC2983::new(bus::BusDevice::new(bus, cs)?))
+    }
+}
+
+impl<SPI, W> LTC2983<SPI, W> where SPI: SpiDevice, W: ConversionWaiter {
+    ///create the driver with a custom strategy to wait for finished conversions
+    pub fn with_waiter(spi_device: SPI, waiter: W) -> Self {
+        LTC2983 {
+            spi_device,
+            waiter,
+            pins: NoPins,
+            state: ConversionState::Idle,
+            channels: Default::default(),
+            faults: Default::default(),
+            stats: Default::default(),
+            fault_callback: None,
+            clock: Box::new(StdClock::default()),
+            linearizations: Default::default(),
+            global_configuration: Default::default(),
+            busy_policy: Default::default(),
+            suspect_policy: Default::default(),
+            multi_conversion_mask: 0,
+            converted: 0,
+            unread: 0,
+            check_converted: false,
+            verify_writes: false,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    ///use the RESET and INTERRUPT pins of the device, see [`LTC2983::hard_reset`] and [`LTC2983::wait_for_interrupt`]
+    pub fn with_pins<RST, INT, D>(self, pins: ControlPins<RST, INT, D>) -> LTC2983<SPI, W, ControlPins<RST, INT, D>>
+    where
+        RST: OutputPin,
+        INT: InputPin,
+        D: DelayNs
+    {
+        LTC2983 {
+            spi_device: self.spi_device,
+            waiter: self.waiter,
+            pins,
+            state: self.state,
+            channels: self.channels,
+            faults: self.faults,
+            stats: self.stats,
+            fault_callback: self.fault_callback,
+            clock: self.clock,
+            linearizations: self.linearizations,
+            global_configuration: self.global_configuration,
+            busy_policy: self.busy_policy,
+            suspect_policy: self.suspect_policy,
+            multi_conversion_mask: self.multi_conversion_mask,
+            converted: self.converted,
+            unread: self.unread,
+            check_converted: self.check_converted,
+            verify_writes: self.verify_writes,
+            retry: self.retry,
+        }
+    }
+}
+
+impl<SPI, W, RST, INT, D> LTC2983<SPI, W, ControlPins<RST, INT, D>>
+where
+    SPI: SpiDevice,
+    W: ConversionWaiter,
+    RST: OutputPin,
+    INT: InputPin,
+    D: DelayNs
+{
+    ///pulse the RESET pin and wait until the device finished its start-up
+    ///
+    ///RESET is held low for 1ms, afterwards the status register is polled with the configured
+    ///[`ConversionWaiter`] until the device reports done. The device comes out of reset with all
+    ///channels unassigned, the configuration remembered by the driver is cleared as well.
+    pub fn hard_reset(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.pins.reset.set_low().map_err(|_| LTC2983Error::PinFault)?;
+        self.pins.delay.delay_ms(1);
+        self.pins.reset.set_high().map_err(|_| LTC2983Error::PinFault)?;
+
+        if !self.wait_until_done()? {
+            return Err(LTC2983Error::ConversionTimeout);
+        }
+        self.forget_configuration();
+        Ok(())
+    }
+
+    ///wait for the INTERRUPT pin to go high, signalling the end of the running conversion
+    ///
+    ///The pin is sampled every 100µs, [`LTC2983Error::ConversionTimeout`] is returned if it is still
+    ///low after `timeout_us`. The status register is not read.
+    pub fn wait_for_interrupt(&mut self, timeout_us: u32) -> Result<(), LTC2983Error<SPI::Error>> {
+        const INTERVAL_US: u32 = 100;
+        let mut waited: u32 = 0;
+        while !self.pins.interrupt.is_high().map_err(|_| LTC2983Error::PinFault)? {
+            if waited >= timeout_us {
+                return Err(LTC2983Error::ConversionTimeout);
+            }
+            self.pins.delay.delay_us(INTERVAL_US);
+            waited = waited.saturating_add(INTERVAL_US);
+        }
+        if self.state == ConversionState::Converting {
+            self.state = ConversionState::Done;
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, W, P> LTC2983<SPI, W, P> where SPI: SpiDevice, W: ConversionWaiter {
+    pub fn conversion_state(&self) -> ConversionState {
+        self.state
+    }
+
+    ///the kind of sensor set up on the channel by this driver, the device is not read
+    pub fn configured_kind(&self, channel: LTC2983Channel) -> Option<SensorKind> {
+        self.channels[channel.index()].as_ref().map(ThermalProbeType::kind)
+    }
+
+    ///unassign all channels and restore the default global configuration
+    ///
+    ///The device has no command to abort a conversion. A running conversion is waited for with the
+    ///[`ConversionWaiter`] first, so it can not write a result after the reset, independent of the
+    ///[`BusyPolicy`]. If it does not finish [`LTC2983Error::ConversionTimeout`] is returned and nothing
+    ///is written, only [`LTC2983::hard_reset`] aborts a conversion.
+    ///
+    ///All channel configuration registers and all result registers are cleared in one write each,
+    ///the multiple channel mask as well. The configuration and fault history remembered by the
+    ///driver is cleared as well.
+    pub fn reset(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        if self.status()?.started() && !self.wait_until_done()? {
+            return Err(LTC2983Error::ConversionTimeout);
+        }
+        self.write_register(LTC2983Channel::CH1.result_address(), &[0; 4 * LTC2983Channel::ALL.len()])?;
+        self.write_register(LTC2983Channel::CH1.start_address(), &[0; 4 * LTC2983Channel::ALL.len()])?;
+        self.write_register(MULTI_CHANNEL_MASK_REGISTER, &[0; 4])?;
+        let (address, bytes) = offline::global_config_write(&GlobalConfiguration::default());
+        self.write_register(address, &bytes)?;
+        self.forget_configuration();
+        Ok(())
+    }
+
+    ///[`reset`](Self::reset) the device, wait until it is ready and configure all channels of `map`
+    ///
+    ///This is meant as recovery when channels keep reporting faults. If the device does not report
+    ///done within the configured [`ConversionWaiter`], [`LTC2983Error::ConversionTimeout`] is returned
+    ///and the channels stay unassigned.
+    pub fn reinitialize(&mut self, map: &ChannelMap) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.reset()?;
+        if !self.wait_until_done()? {
+            return Err(LTC2983Error::ConversionTimeout);
+        }
+        self.apply_config(map)
+    }
+
+    //the device lost its configuration, drop everything that was derived from it
+    fn forget_configuration(&mut self) {
+        self.channels = Default::default();
+        self.faults = Default::default();
+        self.global_configuration = Default::default();
+        self.state = ConversionState::Idle;
+        self.multi_conversion_mask = 0;
+        self.converted = 0;
+        self.unread = 0;
+    }
+
+    ///choose how starting a conversion while the device is busy is handled
+    pub fn set_busy_policy(&mut self, policy: BusyPolicy) {
+        self.busy_policy = policy;
+    }
+
+    ///choose whether suspect results are reported as they are, as valid or as invalid
+    ///
+    ///The policy is applied to every result read, before the linearization and the [`ChannelStats`].
+    ///The fault monitor still sees the faults of the accepted results.
+    pub fn set_suspect_policy(&mut self, policy: SuspectPolicy) {
+        self.suspect_policy = policy;
+    }
+
+    ///repeat failed SPI transfers up to `retries` times before returning the error
+    ///
+    ///`backoff` is called with the number of the upcoming retry (starting at 1) before each retry,
+    ///e.g. to wait with a delay that grows with every attempt. Every transfer of the driver is
+    ///covered, a transfer that fails on all attempts returns the last error.
+    pub fn set_retry_policy(&mut self, retries: usize, backoff: impl FnMut(usize) + Send + 'static) {
+        self.retry = RetryPolicy { retries, backoff: Some(Box::new(backoff)) };
+    }
+
+    ///refuse to read results of channels that were not converted, off by default
+    ///
+    ///After power-up the result registers are cleared and would be decoded as a reading of 0. With
+    ///the check enabled [`LTC2983::read_temperature`] returns [`LTC2983Error::NoConversionYet`] for a
+    ///channel this driver did not start a conversion for, unless the command status register shows
+    ///the channel as converted last, e.g. by an earlier instance of the driver.
+    pub fn set_conversion_check(&mut self, enabled: bool) {
+        self.check_converted = enabled;
+    }
+
+    ///read back the configuration word written by [`LTC2983::setup_channel`], off by default
+    ///
+    ///With the check enabled every setup costs an additional read, a word that differs from the
+    ///written one is reported as [`LTC2983Error::WriteVerifyFailed`] and the channel is not
+    ///remembered as configured.
+    pub fn set_write_verify(&mut self, enabled: bool) {
+        self.verify_writes = enabled;
+    }
+
+    //check the start bit of the device before issuing a new conversion
+    fn ensure_idle(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        match self.busy_policy {
+            BusyPolicy::Interrupt => Ok(()),
+            BusyPolicy::Reject => {
+                if self.status()?.started() {
+                    Err(LTC2983Error::ConversionInProgress)
+                } else {
+                    Ok(())
+                }
+            }
+            BusyPolicy::Queue => {
+                if self.status()?.started() {
+                    self.wait_for_conversion()?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    //configuration registers must not be written during a conversion, with the queue policy the
+    //running conversion is waited for, otherwise it is an error independent of the policy
+    fn ensure_not_converting(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        if !self.status()?.started() {
+            return Ok(());
+        }
+        match self.busy_policy {
+            BusyPolicy::Queue => self.wait_for_conversion(),
+            BusyPolicy::Interrupt | BusyPolicy::Reject => Err(LTC2983Error::DeviceBusy)
+        }
+    }
+
+    ///describe the configured channels and their dependencies as a Graphviz DOT graph
+    ///
+    ///Thermocouples point to their cold junction channel and RTDs to their sense resistor. Only the
+    ///configuration set up by this driver is used, the device is not read.
+    pub fn topology_dot(&self) -> String {
+        let mut dot = String::from("digraph ltc2983 {\n");
+        for (channel, probe) in LTC2983Channel::ALL.iter().zip(self.channels.iter()) {
+            if let Some(probe) = probe {
+                dot.push_str(&format!("    {channel:?} [label=\"{channel:?}\\n{}\"];\n", probe.name()));
+            }
+        }
+        for (channel, probe) in LTC2983Channel::ALL.iter().zip(self.channels.iter()) {
+            let Some(probe) = probe else { continue };
+            if let Some(cold_junction) = probe.thermocouple_parameters().and_then(|param| param.cold_junction_channel) {
+                dot.push_str(&format!("    {channel:?} -> {cold_junction:?} [label=\"cold junction\"];\n"));
+            }
+            if let Some(r_sense) = probe.r_sense_channel() {
+                dot.push_str(&format!("    {channel:?} -> {r_sense:?} [label=\"sense resistor\"];\n"));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    ///monitor the faults of all read results and call `callback` when a channel reports a fault it did not report on the previous read
+    ///
+    ///The callback is edge triggered, a sensor that stays faulty is only reported once. It is
+    ///called with the complete set of faults of the current result.
+    pub fn on_new_fault(&mut self, callback: impl FnMut(LTC2983Channel, FaultFlags) + Send + 'static) {
+        self.fault_callback = Some(Box::new(callback));
+    }
+
+    ///take the timestamps of [`LTC2983::measure`] and the times of [`LTC2983::measure_timed`] from `clock` instead of [`StdClock`]
+    pub fn set_clock(&mut self, clock: impl Clock + Send + 'static) {
+        self.clock = Box::new(clock);
+    }
+
+    ///the current time of the clock, see [`LTC2983::set_clock`]
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    ///correct the valid temperatures read from a channel with `correction`, e.g. a calibration polynomial
+    ///
+    ///The correction gets and returns °C and is applied in software to every valid temperature result
+    ///of the channel before it is returned, suspect and invalid results are left as they are.
+    pub fn set_linearization(&mut self, channel: LTC2983Channel, correction: impl Fn(f32) -> f32 + Send + 'static) {
+        self.linearizations[channel.index()] = Some(Box::new(correction));
+    }
+
+    ///return the temperatures of the channel as read again, see [`LTC2983::set_linearization`]
+    pub fn clear_linearization(&mut self, channel: LTC2983Channel) {
+        self.linearizations[channel.index()] = None;
+    }
+
+    //read device satatus
+    pub fn status(&mut self) -> Result<LTC2983Status, LTC2983Error<SPI::Error>> {
+        Self::read_status(&mut self.spi_device, &mut self.retry)
+    }
+
+    ///wait until the device finished its start-up and accepts commands
+    ///
+    ///After power-up the device takes about 100ms until its status reads done with the start bit
+    ///cleared. The status is polled every millisecond using `delay`, [`LTC2983Error::DeviceNotReady`]
+    ///is returned if it is not ready after `timeout_ms`. Call this before setting up the channels.
+    pub fn wait_until_ready(&mut self, delay: &mut impl DelayNs, timeout_ms: u32) -> Result<(), LTC2983Error<SPI::Error>> {
+        let mut waited: u32 = 0;
+        loop {
+            //a missing or not yet started device reads as all zeros or all ones
+            let status = self.status()?;
+            if status.done() && !status.started() {
+                return Ok(());
+            }
+            if waited >= timeout_ms {
+                return Err(LTC2983Error::DeviceNotReady);
+            }
+            delay.delay_ms(1);
+            waited = waited.saturating_add(1);
+        }
+    }
+
+    fn read_status(spi_device: &mut SPI, retry: &mut RetryPolicy) -> Result<LTC2983Status, LTC2983Error<SPI::Error>> {
+        let [status] = Self::read_fixed::<1>(spi_device, retry, STATUS_REGISTER)?;
+        Ok(LTC2983Status::from(status))
+    }
+
+    //let the waiter poll the done bit, `false` if it gave up
+    fn wait_until_done(&mut self) -> Result<bool, LTC2983Error<SPI::Error>> {
+        let spi_device = &mut self.spi_device;
+        let retry = &mut self.retry;
+        self.waiter.wait::<LTC2983Error<SPI::Error>>(&mut || Ok(Self::read_status(spi_device, retry)?.done()))
+    }
+
+    ///read `len` bytes of consecutive registers starting at `address`
+    ///
+    ///Low level access for registers not covered by the driver, the whole range has to lie
+    ///within the register space 0x000-0x3CF.
+    pub fn read_register(&mut self, address: u16, len: usize) -> Result<Vec<u8>, LTC2983Error<SPI::Error>> {
+        check_register_range(address, len)?;
+        Self::read_bytes(&mut self.spi_device, &mut self.retry, address, len)
+    }
+
+    ///write `data` to consecutive registers starting at `address`, see [`LTC2983::read_register`]
+    pub fn write_register(&mut self, address: u16, data: &[u8]) -> Result<(), LTC2983Error<SPI::Error>> {
+        check_register_range(address, data.len())?;
+        let [addr_high, addr_low] = address.to_be_bytes();
+        let mut frame = vec![LTC2983_WRITE, addr_high, addr_low];
+        frame.extend_from_slice(data);
+        let spi_device = &mut self.spi_device;
+        self.retry.run(|| spi_device.write(&frame))?;
+        Ok(())
+    }
+
+    fn read_bytes(spi_device: &mut SPI, retry: &mut RetryPolicy, address: u16, len: usize) -> Result<Vec<u8>, LTC2983Error<SPI::Error>> {
+        let [addr_high, addr_low] = address.to_be_bytes();
+        let mut frame = vec![LTC2983_READ, addr_high, addr_low];
+        frame.resize(3 + len, 0); //Dummy bytes for reading
+        retry.run(|| {
+            //a failed transfer may have overwritten the frame
+            frame[3..].fill(0);
+            frame[..3].copy_from_slice(&[LTC2983_READ, addr_high, addr_low]);
+            spi_device.transfer_in_place(&mut frame)
+        })?;
+        Ok(frame.split_off(3))
+    }
+
+    //read `N` (at most 4) bytes in a frame on the stack, status polls and result reads happen for every conversion and should not allocate
+    fn read_fixed<const N: usize>(spi_device: &mut SPI, retry: &mut RetryPolicy, address: u16) -> Result<[u8; N], LTC2983Error<SPI::Error>> {
+        let [addr_high, addr_low] = address.to_be_bytes();
+        let mut buffer = [0u8; 7];
+        let frame = &mut buffer[..3 + N];
+        retry.run(|| {
+            //a failed transfer may have overwritten the frame
+            frame[3..].fill(0);
+            frame[..3].copy_from_slice(&[LTC2983_READ, addr_high, addr_low]);
+            spi_device.transfer_in_place(frame)
+        })?;
+        Ok(core::array::from_fn(|i| frame[3 + i]))
+    }
+
+    //write at most 4 bytes in a frame on the stack, the counterpart of `read_fixed` for starting conversions
+    fn write_fixed(&mut self, address: u16, data: &[u8]) -> Result<(), LTC2983Error<SPI::Error>> {
+        let [addr_high, addr_low] = address.to_be_bytes();
+        let mut buffer = [LTC2983_WRITE, addr_high, addr_low, 0, 0, 0, 0];
+        buffer[3..3 + data.len()].copy_from_slice(data);
+        let frame = &buffer[..3 + data.len()];
+        let spi_device = &mut self.spi_device;
+        self.retry.run(|| spi_device.write(frame))?;
+        Ok(())
+    }
+
+    ///wait for the running conversion to finish using the configured [`ConversionWaiter`]
+    pub fn wait_for_conversion(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        if self.wait_until_done()? {
+            if self.state == ConversionState::Converting {
+                self.state = ConversionState::Done;
+            }
+            Ok(())
+        } else {
+            Err(LTC2983Error::ConversionTimeout)
+        }
+    }
+
+    ///check once, without blocking, whether the started conversion is finished
+    ///
+    ///This is meant for interrupt driven operation: start the conversion, let the MCU sleep until
+    ///the INTERRUPT pin of the device goes high and then confirm and read the result.
+    ///
+    ///``` rust,ignore
+    ///    ltc.start_conversion(ltc2983::LTC2983Channel::CH1)?;
+    ///    // sleep until the INTERRUPT pin rises
+    ///    if ltc.poll_done()? {
+    ///        let result = ltc.read_temperature(ltc2983::LTC2983Channel::CH1)?;
+    ///    }
+    ///```
+    ///
+    ///Returns [`LTC2983Error::NoConversionRunning`] if no conversion was started.
+    pub fn poll_done(&mut self) -> Result<bool, LTC2983Error<SPI::Error>> {
+        match self.state {
+            ConversionState::Idle => Err(LTC2983Error::NoConversionRunning),
+            ConversionState::Done => Ok(true),
+            ConversionState::Converting => {
+                if self.status()?.done() {
+                    self.state = ConversionState::Done;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+        }
+    }
+
+    ///start a conversion on the channel, wait for it to finish and read out the result
+    ///
+    ///The measurement is timestamped the moment the finished conversion is observed.
+    pub fn measure(&mut self, channel: LTC2983Channel) -> Result<Measurement, LTC2983Error<SPI::Error>> {
+        self.start_conversion(channel)?;
+        self.wait_for_conversion()?;
+        let timestamp = self.clock.now();
+        Ok(Measurement { channel, result: self.read_temperature(channel)?, timestamp })
+    }
+
+    ///start a conversion on the channel, wait for it to finish and read out the result together with the conversion time
+    ///
+    ///The time is taken from starting the conversion until the finished conversion is observed, so
+    ///it includes the polling granularity of the [`ConversionWaiter`] but not reading the result.
+    pub fn measure_timed(&mut self, channel: LTC2983Channel) -> Result<(LTC2983Result, Duration), LTC2983Error<SPI::Error>> {
+        let started = self.clock.now();
+        self.start_conversion(channel)?;
+        self.wait_for_conversion()?;
+        let elapsed = self.clock.now().saturating_sub(started);
+        Ok((self.read_temperature(channel)?, elapsed))
+    }
+
+    ///[`measure`](Self::measure) a channel, waiting at most `timeout` for the conversion instead of using the [`ConversionWaiter`]
+    ///
+    ///The status is polled every millisecond using `delay`. With `None` the timeout is twice the
+    ///estimated conversion time of the channel for the configured rejection filter, including the
+    ///cold junction of a thermocouple, see [`ThermalProbeType::conversion_time`]. The estimate is at
+    ///least one conversion cycle, a sense resistor has no conversion time of its own. Without an
+    ///estimate, for a channel not set up by this driver, [`LTC2983Error::ChannelUnconfigured`] is
+    ///returned and no conversion is started.
+    pub fn measure_within(&mut self, channel: LTC2983Channel, delay: &mut impl DelayNs, timeout: Option<Duration>) -> Result<Measurement, LTC2983Error<SPI::Error>> {
+        let timeout = match timeout {
+            Some(timeout) => timeout,
+            None => {
+                let estimate = self.estimated_conversion_time(channel).ok_or(LTC2983Error::ChannelUnconfigured(channel))?;
+                estimate.max(self.global_configuration.rejection_filter().cycle_time()) * 2
+            }
+        };
+        self.start_conversion(channel)?;
+        let mut waited = Duration::ZERO;
+        while !self.status()?.done() {
+            if waited >= timeout {
+                return Err(LTC2983Error::ConversionTimeout);
+            }
+            delay.delay_ms(1);
+            waited += Duration::from_millis(1);
+        }
+        if self.state == ConversionState::Converting {
+            self.state = ConversionState::Done;
+        }
+        let timestamp = self.clock.now();
+        Ok(Measurement { channel, result: self.read_temperature(channel)?, timestamp })
+    }
+
+    //conversion time of a configured channel, a thermocouple also converts its cold junction
+    fn estimated_conversion_time(&self, channel: LTC2983Channel) -> Option<Duration> {
+        let rejection = self.global_configuration.rejection_filter();
+        let probe = self.channels[channel.index()].as_ref()?;
+        let cold_junction = probe.thermocouple_parameters()
+            .and_then(|param| param.cold_junction_channel)
+            .and_then(|cold_junction| self.channels[cold_junction.index()].as_ref())
+            .map_or(Duration::ZERO, |cold_junction| cold_junction.conversion_time(&rejection));
+        Some(probe.conversion_time(&rejection) + cold_junction)
+    }
+
+    ///[`measure`](Self::measure) a channel with a different rejection filter, the previous filter is restored afterwards
+    ///
+    ///The global configuration register is read back from the device before it is changed and written
+    ///back unchanged afterwards, also if the measurement failed, so the temperature unit is kept.
+    pub fn measure_with_rejection(&mut self, channel: LTC2983Channel, filter: RejectionFilter) -> Result<Measurement, LTC2983Error<SPI::Error>> {
+        let saved = GlobalConfiguration::from_bits(self.read_register(GLOBAL_CONFIG_REGISTER, 1)?[0]);
+        self.set_global_configuration(saved.rejection(filter))?;
+        let measurement = self.measure(channel);
+        self.set_global_configuration(saved)?;
+        measurement
+    }
+
+    ///configure all channels of `map`
+    ///
+    ///The cold junction and sense resistor references are checked against the map and the already configured
+    ///channels before anything is written, so an invalid map leaves the device untouched.
+    pub fn apply_config(&mut self, map: &ChannelMap) -> Result<(), LTC2983Error<SPI::Error>> {
+        map.iter()
+            .fold(ChannelConfigBuilder::new(), |builder, (channel, probe)| builder.channel(channel, probe.clone()))
+            .commit(self)
+    }
+
+    //write channel configuration
+    pub fn setup_channel(&mut self,
+                         probe: ThermalProbeType,
+                         channel: LTC2983Channel) -> Result<ConfiguredChannel, LTC2983Error<SPI::Error>>
+    {
+        let word = probe.to_config_word().map_err(LTC2983Error::InvalidConfig)?;
+        probe.check_channel(channel).map_err(LTC2983Error::InvalidConfig)?;
+        validate_references(&probe, |channel| self.channels[channel.index()].as_ref()).map_err(LTC2983Error::InvalidConfig)?;
+        self.ensure_not_converting()?;
+        self.write_register(channel.start_address(), &word.to_be_bytes())?;
+        if self.verify_writes {
+            let actual = self.read_channel_config_raw(channel)?;
+            if actual != word {
+                return Err(LTC2983Error::WriteVerifyFailed { channel, expected: word, actual });
+            }
+        }
+        //remember what was programmed, so later reads can make use of the configuration
+        self.channels[channel.index()] = Some(probe.into_written());
+        Ok(ConfiguredChannel(channel))
+    }
+
+    ///unassign the sensor of a channel, the channel is no longer converted or reported as enabled
+    ///
+    ///The channel configuration register is cleared. Channels using it as cold junction or sense
+    ///resistor are not checked, see [`LTC2983::validate_channels`].
+    pub fn disable_channel(&mut self, channel: LTC2983Channel) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.ensure_not_converting()?;
+        self.write_register(channel.start_address(), &[0; 4])?;
+        self.channels[channel.index()] = None;
+        self.faults[channel.index()] = FaultFlags::NONE;
+        Ok(())
+    }
+
+
+    ///check the references between all configured channels (cold junctions and sense resistors)
+    ///
+    ///[`LTC2983::setup_channel`] checks a channel when it is configured, reconfiguring a channel later on
+    ///can still break the channels referring to it.
+    pub fn validate_channels(&self) -> Result<(), LTC2983Error<SPI::Error>> {
+        for probe in self.channels.iter().flatten() {
+            validate_references(probe, |channel| self.channels[channel.index()].as_ref()).map_err(LTC2983Error::InvalidConfig)?;
+        }
+        Ok(())
+    }
+
+    ///read back the configuration programmed into the channel configuration register
+    ///
+    ///Returns `None` for unassigned channels. Thermistors are reported by their type only.
+    pub fn read_probe(&mut self, channel: LTC2983Channel) -> Result<Option<ThermalProbeType>, LTC2983Error<SPI::Error>> {
+        Ok(ThermalProbeType::from_config_word(self.read_channel_config_raw(channel)?))
+    }
+
+    ///check if current source rotation is programmed for the channel, always `false` for channels that are not RTDs
+    pub fn rotation_enabled(&mut self, channel: LTC2983Channel) -> Result<bool, LTC2983Error<SPI::Error>> {
+        Ok(self.read_probe(channel)?
+            .as_ref()
+            .and_then(ThermalProbeType::rtd_parameters)
+            .is_some_and(|param| param.sensor_configuration.current_source_rotation))
+    }
+
+    ///read the 32 bit word of a channel configuration register as programmed
+    ///
+    ///Compare it against [`ThermalProbeType::to_config_word`] to verify the configuration bit for bit.
+    pub fn read_channel_config_raw(&mut self, channel: LTC2983Channel) -> Result<u32, LTC2983Error<SPI::Error>> {
+        let bytes = self.read_register(channel.start_address(), 4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    ///store the channel configuration and the global registers in the EEPROM of the device
+    ///
+    ///Only the LTC2984 has an EEPROM, the LTC2983 ignores the command. The device is unlocked with
+    ///the EEPROM key, then the write command is issued and the status register polled with the
+    ///configured [`ConversionWaiter`] until the device is done. A non-zero EEPROM status register
+    ///afterwards is reported as [`LTC2983Error::EepromFailed`].
+    pub fn save_to_eeprom(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.eeprom_command(EEPROM_WRITE_COMMAND)
+    }
+
+    ///load the configuration stored by [`LTC2983::save_to_eeprom`] into the device registers
+    ///
+    ///Afterwards the channel configuration and the global configuration are read back from the
+    ///device, so the driver knows the restored sensors.
+    pub fn restore_from_eeprom(&mut self) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.eeprom_command(EEPROM_READ_COMMAND)?;
+        for channel in LTC2983Channel::all() {
+            self.channels[channel.index()] = self.read_probe(channel)?;
+        }
+        let config = self.read_register(GLOBAL_CONFIG_REGISTER, 1)?;
+        self.global_configuration = GlobalConfiguration::from_bits(config[0]);
+        Ok(())
+    }
+
+    fn eeprom_command(&mut self, command: u8) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.ensure_idle()?;
+        self.write_register(EEPROM_KEY_REGISTER, &EEPROM_KEY.to_be_bytes())?;
+        self.write_register(STATUS_REGISTER, &[command])?;
+        if !self.wait_until_done()? {
+            return Err(LTC2983Error::ConversionTimeout);
+        }
+        match self.read_register(EEPROM_STATUS_REGISTER, 1)?[0] {
+            0 => Ok(()),
+            status => Err(LTC2983Error::EepromFailed(status))
+        }
+    }
+
+    //check if the channel is configured
+    pub fn channel_enabled(&mut self, channel: LTC2983Channel) -> bool {
+        match self.read_register(channel.start_address(), 1) {
+            Ok(config) => sensor_type_assigned(config[0]),
+            Err(_err) => {
+                //on communication error assume unconfigured channel
+                false
+            }
+        }
+    }
+
+    ///the channels the device has a sensor type assigned to, read from the configuration registers
+    ///
+    ///All configuration registers are read in a single transfer. Unlike [`LTC2983::channel_enabled`]
+    ///a communication error is returned instead of being treated as unconfigured.
+    pub fn enabled_channels(&mut self) -> Result<Vec<LTC2983Channel>, LTC2983Error<SPI::Error>> {
+        Ok(self.assigned_sensor_types()?.into_iter().map(|(channel, _)| channel).collect())
+    }
+
+    ///the kind of sensor the device has assigned to each channel, read from the configuration registers
+    ///
+    ///All configuration registers are read in a single transfer, channels with an unused sensor
+    ///type code are left out. Unlike [`LTC2983::configured_kind`] this also reports channels set up
+    ///by an earlier instance of the driver or restored from the EEPROM.
+    pub fn read_channel_kinds(&mut self) -> Result<Vec<(LTC2983Channel, SensorKind)>, LTC2983Error<SPI::Error>> {
+        Ok(self.assigned_sensor_types()?.into_iter()
+            .filter_map(|(channel, code)| Some((channel, SensorKind::from_type_code(code as u64)?)))
+            .collect())
+    }
+
+    //the enabled channels with the sensor type code of their configuration word
+    fn assigned_sensor_types(&mut self) -> Result<Vec<(LTC2983Channel, u8)>, LTC2983Error<SPI::Error>> {
+        let config = self.read_register(LTC2983Channel::CH1.start_address(), 4 * LTC2983Channel::ALL.len())?;
+        Ok(LTC2983Channel::all()
+            .zip(config.chunks_exact(4))
+            .filter(|(_, word)| sensor_type_assigned(word[0]))
+            .map(|(channel, word)| (channel, word[0] >> 3))
+            .collect())
+    }
+
+    ///convert all channels the device has a sensor assigned to, see [`LTC2983::enabled_channels`]
+    ///
+    ///Sense resistors are left out, they are measured as part of their RTD or thermistor and do
+    ///not deliver a result of their own. Returns [`LTC2983Error::NoChannelsConfigured`] instead
+    ///of starting a conversion with an empty mask. The converted channels are returned.
+    pub fn start_all_configured(&mut self) -> Result<Vec<LTC2983Channel>, LTC2983Error<SPI::Error>> {
+        let sense_resistor = ThermalProbeType::SenseResistor(0.).identifier() as u8;
+        let channels: Vec<_> = self.assigned_sensor_types()?.into_iter()
+            .filter(|(_, sensor_type)| *sensor_type != sense_resistor)
+            .map(|(channel, _)| channel)
+            .collect();
+        if channels.is_empty() {
+            return Err(LTC2983Error::NoChannelsConfigured);
+        }
+        self.start_multi_conversion(&channels)?;
+        Ok(channels)
+    }
+
+    pub fn start_conversion(&mut self, channel: LTC2983Channel) -> Result<(), LTC2983Error<SPI::Error>> {
+        self.ensure_idle()?;
+        //start measurement, the upper 3 bits (0b100) start the conversion and the lower 5 bits select the channel
+        self.write_fixed(STATUS_REGISTER, &[0x80 | channel.identifier() as u8])?;
+        self.state = ConversionState::Converting;
+        self.converted |= channel.mask();
+        self.unread |= channel.mask();
+
+        Ok(())
+    }
+
+    ///write the global configuration register (temperature unit and rejection filter)
+    pub fn set_global_configuration(&mut self, config: GlobalConfiguration) -> Result<(), LTC2983Error<SPI::Error>> {
+        let (address, bytes) = offline::global_config_write(&config);
+        self.write_register(address, &bytes)?;
+        self.global_configuration = config;
+        Ok(())
+    }
+
+    ///the global configuration last written by [`LTC2983::set_global_configuration`]
+    pub fn global_configuration(&self) -> GlobalConfiguration {
+        self.global_configuration
+    }
+
+    ///set the delay the device waits after switching the input mux before a conversion starts
+    ///
+    ///The register has a resolution of 100µs, the delay is rounded up to the next step and limited
+    ///to the maximum of 25.5ms.
+    pub fn set_mux_delay(&mut self, delay_us: u32) -> Result<(), LTC2983Error<SPI::Error>> {
+        let (address, bytes) = offline::mux_delay_write(delay_us);
+        self.write_register(address, &bytes)
+    }
+
+    ///convert all `channels` one after the other
+    ///
+    ///The result registers of the channels are cleared before the conversion starts, so
+    ///[`LTC2983::completed_channels`] can tell which results are already available. All channels
+    ///need a sensor, set up by this driver or found in the configuration registers (set up by an
+    ///earlier instance or restored from the EEPROM), otherwise [`LTC2983Error::ChannelUnconfigured`]
+    ///is returned for the first one without and nothing is written.
+    pub fn start_multi_conversion(&mut self, channels: &[LTC2983Channel]) -> Result<(), LTC2983Error<SPI::Error>> {
+        if channels.iter().any(|channel| self.channels[channel.index()].is_none()) {
+            let enabled = self.enabled_channels()?;
+            if let Some(channel) = channels.iter().find(|channel| self.channels[channel.index()].is_none() && !enabled.contains(channel)) {
+                return Err(LTC2983Error::ChannelUnconfigured(*channel));
+            }
+        }
+        self.ensure_idle()?;
+        let mut mask: u32 = 0x0;
+        for chan in channels {
+            mask |= chan.mask();
+        }
+        self.write_fixed(MULTI_CHANNEL_MASK_REGISTER, &mask.to_be_bytes())?;
+        for channel in LTC2983Channel::all().filter(|channel| mask & channel.mask() != 0) {
+            self.write_fixed(channel.result_address(), &[0; 4])?;
+        }
+        self.multi_conversion_mask = mask;
+
+        //start bit set and channel selection 0 start a conversion of all channels in the mask
+        self.write_fixed(STATUS_REGISTER, &[0x80])?;
+        self.state = ConversionState::Converting;
+        self.converted |= mask;
+        self.unread |= mask;
+        Ok(())
+    }
+
+    ///read back the channels selected in the multiple channel mask register (0x0F4)
+    pub fn read_multi_channel_mask(&mut self) -> Result<Vec<LTC2983Channel>, LTC2983Error<SPI::Error>> {
+        let bytes = self.read_register(MULTI_CHANNEL_MASK_REGISTER, 4)?;
+        let mask = u32::from_be_bytes(bytes.try_into().unwrap());
+        Ok(LTC2983Channel::all().filter(|channel| mask & channel.mask() != 0).collect())
+    }
+
+    ///read back all configuration registers, e.g. to attach the device state to a bug report
+    ///
+    ///The global registers from 0x0F0 to 0x0FF and the channel configuration registers are read in
+    ///a burst each.
+    pub fn snapshot(&mut self) -> Result<DeviceSnapshot, LTC2983Error<SPI::Error>> {
+        let global = self.read_register(GLOBAL_CONFIG_REGISTER, (MUX_CONFIG_DELAY_REGISTER - GLOBAL_CONFIG_REGISTER) as usize + 1)?;
+        let offset = |register: u16| (register - GLOBAL_CONFIG_REGISTER) as usize;
+        let config = self.read_register(LTC2983Channel::CH1.start_address(), 4 * LTC2983Channel::ALL.len())?;
+
+        let mut channel_config = [0; 20];
+        for (word, bytes) in channel_config.iter_mut().zip(config.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        let mask = offset(MULTI_CHANNEL_MASK_REGISTER);
+        Ok(DeviceSnapshot {
+            global_configuration: global[offset(GLOBAL_CONFIG_REGISTER)],
+            multi_channel_mask: u32::from_be_bytes(global[mask..mask + 4].try_into().unwrap()),
+            mux_delay: global[offset(MUX_CONFIG_DELAY_REGISTER)],
+            channel_config
+        })
+    }
+
+    ///read back the global configuration register (0x0F0) and the mux delay register (0x0FF)
+    ///
+    ///Both registers are read in a single burst. Unlike [`LTC2983::global_configuration`] this
+    ///shows what the device actually uses, e.g. after an EEPROM restore.
+    pub fn read_global_settings(&mut self) -> Result<GlobalSettings, LTC2983Error<SPI::Error>> {
+        let global = self.read_register(GLOBAL_CONFIG_REGISTER, (MUX_CONFIG_DELAY_REGISTER - GLOBAL_CONFIG_REGISTER) as usize + 1)?;
+        let config = GlobalConfiguration::from_bits(global[0]);
+        Ok(GlobalSettings {
+            temperature_unit: config.temperature_unit(),
+            rejection_filter: config.rejection_filter(),
+            mux_delay_us: global[(MUX_CONFIG_DELAY_REGISTER - GLOBAL_CONFIG_REGISTER) as usize] as u32 * 100
+        })
+    }
+
+    ///the channels of the last [`LTC2983::start_multi_conversion`] whose results are available
+    ///
+    ///A channel is done once the device wrote a result word, i.e. the fault and valid bits are no
+    ///longer all cleared. This allows reading the results while the remaining channels are still converting.
+    pub fn completed_channels(&mut self) -> Result<Vec<LTC2983Channel>, LTC2983Error<SPI::Error>> {
+        let mask = self.multi_conversion_mask;
+        if self.status()?.done() {
+            return Ok(LTC2983Channel::all().filter(|channel| mask & channel.mask() != 0).collect());
+        }
+        let mut completed = Vec::new();
+        for channel in LTC2983Channel::all().filter(|channel| mask & channel.mask() != 0) {
+            if self.read_register(channel.result_address(), 1)?[0] != 0 {
+                completed.push(channel);
+            }
+        }
+        Ok(completed)
+    }
+
+    ///read the result of the last conversion of a channel
+    ///
+    ///Channels configured as [`ThermalProbeType::DirectADC`] report a [`Reading::Voltage`], sense
+    ///resistors a [`Reading::Resistance`] and all others a [`Reading::Temperature`]. The kind is
+    ///taken from the configuration set up by this driver. Temperatures are always returned in °C, also if the device is
+    ///configured to report °F.
+    pub fn read_temperature(&mut self, channel: LTC2983Channel) -> Result<LTC2983Result, LTC2983Error<SPI::Error>> {
+        if self.check_converted && self.converted & channel.mask() == 0 && self.status()?.active_channel() != Some(channel) {
+            return Err(LTC2983Error::NoConversionYet(channel));
+        }
+        let bytes = self.read_result_bytes(channel)?;
+        Ok(self.take_result(channel, bytes))
+    }
+
+    ///check whether the conversion is done and if so return the result of `channel`
+    ///
+    ///The status register and the result register are read in a single transfer, the burst covers
+    ///all registers from 0x000 up to the result of the channel. `None` while the conversion is
+    ///still running.
+    pub fn poll_and_read(&mut self, channel: LTC2983Channel) -> Result<Option<LTC2983Result>, LTC2983Error<SPI::Error>> {
+        let end = channel.result_address() as usize + 4;
+        let bytes = self.read_register(STATUS_REGISTER, end)?;
+        if !LTC2983Status::from(bytes[0]).done() {
+            return Ok(None);
+        }
+        if self.state == ConversionState::Converting {
+            self.state = ConversionState::Done;
+        }
+        Ok(Some(self.take_result(channel, bytes[end - 4..].try_into().unwrap())))
+    }
+
+    ///read the result of a channel only if it belongs to the latest conversion
+    ///
+    ///The status register and the result register are read in a single transfer like in
+    ///[`LTC2983::poll_and_read`]. [`LTC2983Error::StaleResult`] is returned unless the conversion is
+    ///done, the status shows the channel as selected by the last command (directly or by the mask of
+    ///a multi channel conversion), the device wrote a result word and the result was not read since
+    ///this driver started the conversion. Each conversion is returned at most once.
+    pub fn read_latest(&mut self, channel: LTC2983Channel) -> Result<LTC2983Result, LTC2983Error<SPI::Error>> {
+        let end = channel.result_address() as usize + 4;
+        let bytes = self.read_register(STATUS_REGISTER, end)?;
+        let status = LTC2983Status::from(bytes[0]);
+        let result: [u8; 4] = bytes[end - 4..].try_into().unwrap();
+        let selected = match status.active_channel() {
+            Some(active) => active == channel,
+            None => self.multi_conversion_mask & channel.mask() != 0
+        };
+        //a result word with all fault and valid bits cleared was not written by the device
+        if !status.done() || status.started() || !selected || result[0] == 0 || self.unread & channel.mask() == 0 {
+            return Err(LTC2983Error::StaleResult(channel));
+        }
+        if self.state == ConversionState::Converting {
+            self.state = ConversionState::Done;
+        }
+        Ok(self.take_result(channel, result))
+    }
+
+    //decode a result word read from the device and account for it
+    fn take_result(&mut self, channel: LTC2983Channel, bytes: [u8; 4]) -> LTC2983Result {
+        self.unread &= !channel.mask();
+        let decoded = decode_result(bytes, self.channels[channel.index()].as_ref(), &self.global_configuration);
+        let faults = decoded.faults();
+        let mut result = self.suspect_policy.apply(decoded);
+        if let (LTC2983Result::Valid(Reading::Temperature(Celsius(celsius))), Some(correction)) = (&mut result, &self.linearizations[channel.index()]) {
+            *celsius = correction(*celsius);
+        }
+        if self.state == ConversionState::Done {
+            self.state = ConversionState::Idle;
+        }
+        self.track_faults(channel, faults);
+        if let LTC2983Result::Valid(Reading::Temperature(Celsius(celsius))) = result {
+            self.stats[channel.index()] = Some(ChannelStats::update(self.stats[channel.index()], celsius));
+        }
+        result
+    }
+
+    ///the lowest and highest valid temperature in °C read from the channel since the last [`reset_stats`](Self::reset_stats)
+    ///
+    ///Every read of a result counts, invalid and suspect results are left out. `None` until the
+    ///first valid temperature of the channel was read.
+    pub fn channel_stats(&self, channel: LTC2983Channel) -> Option<ChannelStats> {
+        self.stats[channel.index()]
+    }
+
+    ///start over collecting the [`ChannelStats`] of a channel
+    pub fn reset_stats(&mut self, channel: LTC2983Channel) {
+        self.stats[channel.index()] = None;
+    }
+
+    ///read the result of the last conversion of a channel set up before, see [`ConfiguredChannel`]
+    pub fn read_configured(&mut self, channel: &ConfiguredChannel) -> Result<LTC2983Result, LTC2983Error<SPI::Error>> {
+        self.read_temperature(channel.channel())
+    }
+
+    ///read the result of the last conversion of a RTD or thermistor channel and tell whether it is open or shorted
+    ///
+    ///`None` if the result shows no wiring fault or the channel is not configured as RTD or thermistor,
+    ///see [`ThermalProbeType::wiring_fault`].
+    pub fn read_wiring_fault(&mut self, channel: LTC2983Channel) -> Result<Option<WiringFault>, LTC2983Error<SPI::Error>> {
+        let faults = self.read_temperature(channel)?.faults();
+        Ok(self.channels[channel.index()].as_ref().and_then(|probe| probe.wiring_fault(faults)))
+    }
+
+    ///read the result of the last conversion of a channel and whether its fault flags differ from the previous read
+    ///
+    ///The first read of a channel is compared against no faults. The faults are compared as the device
+    ///reported them, before the [`SuspectPolicy`] turned a suspect result into a valid or invalid one.
+    pub fn read_temperature_with_change(&mut self, channel: LTC2983Channel) -> Result<(LTC2983Result, bool), LTC2983Error<SPI::Error>> {
+        let previous = self.faults[channel.index()];
+        let result = self.read_temperature(channel)?;
+        //take_result keeps the faults reported by the device
+        let changed = self.faults[channel.index()] != previous;
+        Ok((result, changed))
+    }
+
+    //remember the faults of the channel and report newly appeared ones to the fault monitor
+    fn track_faults(&mut self, channel: LTC2983Channel, faults: FaultFlags) {
+        let previous = std::mem::replace(&mut self.faults[channel.index()], faults);
+        if !faults.new_since(previous).is_empty() {
+            #[cfg(feature = "log")]
+            log::warn!("channel {channel:?} reports new faults: {faults}");
+            if let Some(callback) = self.fault_callback.as_mut() {
+                callback(channel, faults);
+            }
+        }
+    }
+
+    //read the raw 32 bit result word of a channel (fault byte followed by the 24 bit value)
+    fn read_result_bytes(&mut self, channel: LTC2983Channel) -> Result<[u8; 4], LTC2983Error<SPI::Error>> {
+        Self::read_fixed(&mut self.spi_device, &mut self.retry, channel.result_address())
+    }
+
+    ///read the temperature of a channel converted to `unit`
+    ///
+    ///Only valid results are converted, for suspect and invalid results [`LTC2983Error::FaultyResult`] is returned.
+    pub fn read_temperature_as(&mut self, channel: LTC2983Channel, unit: TemperatureUnit) -> Result<f32, LTC2983Error<SPI::Error>> {
+        match self.read_temperature(channel)? {
+            LTC2983Result::Valid(Reading::Temperature(Celsius(celsius))) => Ok(unit.from_celsius(celsius)),
+            LTC2983Result::Valid(Reading::Voltage(_) | Reading::Resistance(_)) => Err(LTC2983Error::NotATemperature(channel)),
+            result => Err(LTC2983Error::FaultyResult(result.faults())),
+        }
+    }
+
+    ///read the resistance measured on a sense resistor channel
+    ///
+    ///Unlike temperatures (1/1024 °C) the result holds the resistance as unsigned 24 bit value in
+    ///1/64 Ω steps, so the whole 0 to 131072 Ω a sense resistor can be configured with fits.
+    ///Returns [`LTC2983Error::SensorKindMismatch`] for any other kind of channel and
+    ///[`LTC2983Error::FaultyResult`] for suspect and invalid results.
+    pub fn read_resistance(&mut self, channel: LTC2983Channel) -> Result<Ohms, LTC2983Error<SPI::Error>> {
+        self.expect_kind(channel, SensorKind::SenseResistor)?;
+        match self.read_temperature(channel)? {
+            LTC2983Result::Valid(Reading::Resistance(ohms)) => Ok(ohms),
+            result => Err(LTC2983Error::FaultyResult(result.faults())),
+        }
+    }
+
+    ///read the voltage measured on a direct ADC channel
+    ///
+    ///Returns [`LTC2983Error::SensorKindMismatch`] for any other kind of channel and
+    ///[`LTC2983Error::FaultyResult`] for suspect and invalid results.
+    pub fn read_voltage(&mut self, channel: LTC2983Channel) -> Result<Volts, LTC2983Error<SPI::Error>> {
+        self.expect_kind(channel, SensorKind::DirectADC)?;
+        match self.read_temperature(channel)? {
+            LTC2983Result::Valid(Reading::Voltage(volts)) => Ok(volts),
+            result => Err(LTC2983Error::FaultyResult(result.faults())),
+        }
+    }
+
+    //check that the channel is set up with the kind of sensor a typed read is meant for
+    fn expect_kind(&self, channel: LTC2983Channel, expected: SensorKind) -> Result<(), LTC2983Error<SPI::Error>> {
+        match self.configured_kind(channel) {
+            None => Err(LTC2983Error::ChannelUnconfigured(channel)),
+            Some(actual) if actual != expected => Err(LTC2983Error::SensorKindMismatch { expected, actual }),
+            Some(_) => Ok(())
+        }
+    }
+
+    ///read the result of a thermocouple and of its cold junction sensor
+    ///
+    ///The device converts the cold junction sensor together with the thermocouple and stores its
+    ///temperature in the result register of the cold junction channel, so after the conversion of
+    ///the thermocouple both results belong together. This does not start a conversion.
+    pub fn read_with_cold_junction(&mut self, channel: LTC2983Channel) -> Result<ColdJunctionReading, LTC2983Error<SPI::Error>> {
+        let probe = self.channels[channel.index()].as_ref().ok_or(LTC2983Error::ChannelUnconfigured(channel))?;
+        let cold_junction_channel = probe.thermocouple_parameters()
+            .and_then(|param| param.cold_junction_channel)
+            .ok_or(LTC2983Error::NoColdJunction(channel))?;
+
+        let thermocouple = self.read_temperature(channel)?;
+        let cold_junction = self.read_temperature(cold_junction_channel)?;
+        Ok(ColdJunctionReading { thermocouple, cold_junction })
+    }
+
+    ///calculate the voltage drop across a sense resistor at the excitation current of the RTD or thermistor using it
+    ///
+    ///The expected drop is calculated from the configured resistance and the excitation current of the
+    ///first configured RTD or thermistor referencing the sense resistor, 0 for an auto ranging
+    ///thermistor. If the result register of the sense
+    ///resistor channel holds a valid reading it is taken as the measured resistance and the measured
+    ///drop is returned as well. A mismatch between both indicates a fault of the current source.
+    pub fn read_sense_voltage(&mut self, rsense_channel: LTC2983Channel) -> Result<SenseVoltage, LTC2983Error<SPI::Error>> {
+        let resistance = match &self.channels[rsense_channel.index()] {
+            Some(ThermalProbeType::SenseResistor(resistance)) => *resistance,
+            _ => return Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistorUnconfigured(rsense_channel))),
+        };
+        let microamps = self.channels.iter().flatten()
+            .find_map(|probe| match (probe.rtd_parameters(), probe.thermistor_parameters()) {
+                (Some(param), _) if param.r_sense_channel == rsense_channel => Some(param.excitation_current.microamps()),
+                (_, Some(param)) if param.r_sense_channel == rsense_channel => Some(param.excitation_current.microamps()),
+                _ => None
+            })
+            .ok_or(LTC2983Error::SenseResistorUnused(rsense_channel))?;
+
+        let bytes = self.read_result_bytes(rsense_channel)?;
+        let measured = if bytes[0] == 0x01 {
+            Some(expected_sense_voltage(resistance_from_result_bytes(&bytes).to_num(), microamps))
+        } else {
+            None
+        };
+
+        Ok(SenseVoltage { expected: expected_sense_voltage(resistance, microamps), measured })
+    }
+
+    ///convert the channels one after the other, finishing the whole scan by `deadline`
+    ///
+    ///`deadline` is a time of the driver's [`Clock`], e.g. `ltc.now() + Duration::from_millis(500)`.
+    ///The time left is split evenly between the channels still to convert, so a channel that does
+    ///not finish in its share is reported as [`ScanOutcome::Timeout`]. The device keeps converting
+    ///that channel, the next channel is only started once it is done. If that does not happen by
+    ///`deadline` the remaining channels are reported as [`ScanOutcome::Timeout`] without being started.
+    pub fn scan_all_with_deadline(&mut self, channels: &[LTC2983Channel], deadline: Duration) -> Result<Vec<(LTC2983Channel, ScanOutcome)>, LTC2983Error<SPI::Error>> {
+        let mut results = Vec::with_capacity(channels.len());
+        let mut busy = false;
+
+        for (i, &channel) in channels.iter().enumerate() {
+            if busy {
+                busy = !self.poll_done_until(deadline)?;
+            }
+            let now = self.clock.now();
+            if busy || now >= deadline {
+                results.push((channel, ScanOutcome::Timeout));
+                continue;
+            }
+            let channel_deadline = now + (deadline - now) / (channels.len() - i) as u32;
+
+            self.start_conversion(channel)?;
+            if self.poll_done_until(channel_deadline)? {
+                results.push((channel, ScanOutcome::Completed(self.read_temperature(channel)?)));
+            } else {
+                busy = true;
+                results.push((channel, ScanOutcome::Timeout));
+            }
+        }
+
+        Ok(results)
+    }
+
+    //poll the done bit until it is set or the clock reached `deadline`, the status is read at least once
+    fn poll_done_until(&mut self, deadline: Duration) -> Result<bool, LTC2983Error<SPI::Error>> {
+        let mut done = self.poll_done()?;
+        while !done && self.clock.now() < deadline {
+            done = self.poll_done()?;
+        }
+        Ok(done)
+    }
+
+    ///read the results of `channels`, each tagged with its channel, see [`LTC2983::read_temperature`]
+    pub fn read_multi_temperature(&mut self, channels: &[LTC2983Channel]) -> Vec<ChannelResult<SPI::Error>> {
+        channels.iter().map(|chan| {
+            (*chan, self.read_temperature(*chan))
+        }).collect()
+    }
+
+    ///read the last result of every channel as signed hundredths of a degree Celsius, indexed by channel
+    ///
+    ///The compact representation covers -327.67°C to 327.67°C in steps of 0.01°C. Unconfigured
+    ///channels, [`ThermalProbeType::DirectADC`] channels, suspect or invalid results and temperatures
+    ///outside of that range are reported as [`PACKED_FAULT`].
+    pub fn read_all_packed(&mut self) -> Result<[i16; 20], LTC2983Error<SPI::Error>> {
+        let mut packed = [PACKED_FAULT; 20];
+        for channel in LTC2983Channel::all() {
+            if self.channels[channel.index()].is_none() {
+                continue;
+            }
+            if let LTC2983Result::Valid(Reading::Temperature(Celsius(t))) = self.read_temperature(channel)? {
+                packed[channel.index()] = pack_hundredths(t);
+            }
+        }
+        Ok(packed)
+    }
+
+    ///convert all channels configured by this driver and read their results
+    ///
+    ///All channels are converted in one multi channel conversion and the results are read in a
+    ///single burst from the first to the last result register. Unconfigured channels and sense
+    ///resistors are not part of the map, without any of the other channels the device is not accessed.
+    pub fn read_all(&mut self) -> Result<BTreeMap<LTC2983Channel, LTC2983Result>, LTC2983Error<SPI::Error>> {
+        let channels: Vec<_> = LTC2983Channel::all()
+            .filter(|channel| self.channels[channel.index()].as_ref().is_some_and(|probe| !matches!(probe, ThermalProbeType::SenseResistor(_))))
+            .collect();
+        let (Some(first), Some(last)) = (channels.first().copied(), channels.last().copied()) else {
+            return Ok(BTreeMap::new());
+        };
+
+        self.start_multi_conversion(&channels)?;
+        self.wait_for_conversion()?;
+        let start = first.result_address();
+        let bytes = self.read_register(start, (last.result_address() - start) as usize + 4)?;
+        Ok(channels.into_iter().map(|channel| {
+            let offset = (channel.result_address() - start) as usize;
+            (channel, self.take_result(channel, bytes[offset..offset + 4].try_into().unwrap()))
+        }).collect())
+    }
+
+    
+    ///do multiple rounds of conversion for a channel then calculate the average of the temperatures read out
+    ///
+    ///Fails if any of the readings is not valid, see [`LTC2983::get_temperature_avg_detailed`] for an
+    ///average that skips those.
+    pub fn get_temperature_avg(&mut self, channel: LTC2983Channel, rounds: usize) -> Result<f32, LTC2983Error<SPI::Error>> {
+        let samples = self.collect_samples(channel, rounds)?;
+        if samples.rejected > 0 {
+            return Err(LTC2983Error::SamplesRejected(samples.rejected, rounds));
+        }
+
+        mean(&samples.values).ok_or(LTC2983Error::NoValidSamples)
+    }
+
+    ///do `rounds` conversions for a channel and average the valid readings, reporting how many readings were rejected
+    pub fn get_temperature_avg_detailed(&mut self, channel: LTC2983Channel, rounds: usize) -> Result<TemperatureAverage, LTC2983Error<SPI::Error>> {
+        let samples = self.collect_samples(channel, rounds)?;
+
+        Ok(TemperatureAverage {
+            average: mean(&samples.values).ok_or(LTC2983Error::NoValidSamples)?,
+            valid_samples: samples.values.len(),
+            rejected: samples.rejected
+        })
+    }
+
+    ///do `rounds` conversions for a channel and calculate mean, median, min, max and standard deviation of the valid readings
+    pub fn get_temperature_stats(&mut self, channel: LTC2983Channel, rounds: usize) -> Result<TemperatureStats, LTC2983Error<SPI::Error>> {
+        let samples = self.collect_samples(channel, rounds)?;
+        TemperatureStats::from_samples(&samples).ok_or(LTC2983Error::NoValidSamples)
+    }
+
+    ///do `rounds` conversions for a channel and average the valid readings within `sigma` standard deviations of their mean
+    ///
+    ///Returns [`LTC2983Error::TooFewSamples`] if less than 3 readings are left after dropping the outliers.
+    pub fn get_temperature_avg_filtered(&mut self, channel: LTC2983Channel, rounds: usize, sigma: f32) -> Result<f32, LTC2983Error<SPI::Error>> {
+        let samples = self.collect_samples(channel, rounds)?;
+        let kept = reject_outliers(&samples.values, sigma);
+        if kept.len() < 3 {
+            return Err(LTC2983Error::TooFewSamples(kept.len()));
+        }
+
+        mean(&kept).ok_or(LTC2983Error::NoValidSamples)
+    }
+
+    //do `rounds` conversions of a channel, keeping the valid readings and counting the others
+    fn collect_samples(&mut self, channel: LTC2983Channel, rounds: usize) -> Result<Samples, LTC2983Error<SPI::Error>> {
+        let mut samples = Samples { values: Vec::with_capacity(rounds), rejected: 0 };
+
+        for _ in 0..rounds {
+            self.start_conversion(channel)?;
+
+            self.wait_for_conversion()?;
+
+            match self.read_temperature(channel)? {
+                LTC2983Result::Valid(temp) => samples.values.push(temp.value()),
+                LTC2983Result::Invalid(_) | LTC2983Result::Suspect(_, _) => samples.rejected += 1,
+            }
+        }
+
+        Ok(samples)
+    }
+
+    ///do multiple rounds of conversion for a channel then calculate the average in fixed point arithmetic
+    ///
+    ///The readings are accumulated in a 64 bit fixed point value and a device reporting °F is converted
+    ///to °C in fixed point, so no floating point operation is involved as long as the channel has no
+    ///linearization. This is meant for targets without a FPU. Like the other reads the average is in
+    ///°C, the [`SuspectPolicy`] decides which results count and the diode offset is applied. Sense
+    ///resistor and direct ADC channels are rejected with [`LTC2983Error::NotATemperature`].
+    pub fn get_temperature_avg_fixed(&mut self, channel: LTC2983Channel, rounds: usize) -> Result<FixedI32<U10>, LTC2983Error<SPI::Error>> {
+        if matches!(self.configured_kind(channel), Some(SensorKind::SenseResistor | SensorKind::DirectADC)) {
+            return Err(LTC2983Error::NotATemperature(channel));
+        }
+        let mut values = Vec::new();
+        let mut rejected = 0;
+
+        for _ in 0..rounds {
+            self.start_conversion(channel)?;
+
+            self.wait_for_conversion()?;
+
+            let bytes = self.read_result_bytes(channel)?;
+            match self.take_result(channel, bytes) {
+                //the linearization works on floating point temperatures
+                LTC2983Result::Valid(Reading::Temperature(Celsius(celsius))) if self.linearizations[channel.index()].is_some() => {
+                    values.push(FixedI32::<U10>::from_num(celsius));
+                }
+                LTC2983Result::Valid(_) => {
+                    let unit = self.global_configuration.temperature_unit();
+                    values.push(fixed_celsius_from_result_bytes(&bytes, self.channels[channel.index()].as_ref(), unit));
+                }
+                _ => rejected += 1,
+            }
+        }
+        if rejected > 0 {
+            return Err(LTC2983Error::SamplesRejected(rejected, rounds));
+        }
+
+        average_fixed(&values).ok_or(LTC2983Error::NoValidSamples)
+    }
+
+    ///do multiple rounds of conversion for multiple channels then calculate the average of the temperatures read out
+    pub fn get_multi_temperature_avg(&mut self, channels: &[LTC2983Channel], rounds: usize) -> Result<Vec<f32>, LTC2983Error<SPI::Error>> {
+        self.get_multi_temperature_avg_with_progress(channels, rounds, |_, _| {})
+    }
+
+    ///[`get_multi_temperature_avg`](Self::get_multi_temperature_avg) calling `progress` with the results of every round
+    ///
+    ///`progress` gets the number of the round (starting at 0) and the results in the order of
+    ///`channels`. A round with an invalid or suspect result is not counted and repeated, `progress`
+    ///then sees the same round number again. At most `rounds` rounds are repeated before
+    ///[`LTC2983Error::SamplesRejected`] is returned, a result that could not be read fails right away.
+    pub fn get_multi_temperature_avg_with_progress(&mut self,
+                                                   channels: &[LTC2983Channel],
+                                                   rounds: usize,
+                                                   mut progress: impl FnMut(usize, &[LTC2983Result])) -> Result<Vec<f32>, LTC2983Error<SPI::Error>> {
+        let mut values = Vec::new();
+        let mut r = 0;
+        let mut rejected = 0;
+
+        while r < rounds {
+            self.start_multi_conversion(channels)?;
+            self.wait_for_conversion()?;
+            let mut v = Vec::with_capacity(channels.len());
+            let mut results = Vec::with_capacity(channels.len());
+            for (_, res) in self.read_multi_temperature(channels) {
+                let res = res?;
+                if let LTC2983Result::Valid(temp) = &res {
+                    v.push(temp.value());
+                }
+                results.push(res);
+            }
+            progress(r, &results);
+            if v.len() == channels.len() {
+                values.push(v);
+                r += 1;
+            } else {
+                rejected += 1;
+                if rejected > rounds { // every round got its retry, a channel does not deliver valid readings
+                    return Err(LTC2983Error::SamplesRejected(rejected, rounds));
+                }
+            }
+        }
+
+        values.into_iter().reduce(|acc, e| {
+            acc.iter().zip(e.iter()).map(|(&a, &b)| a+b).collect::<Vec<f32>>() // do a component wise add of the values
+        }).map(|v| {
+            v.iter().map(|x| x/(rounds as f32)).collect() // calculate average by dividing by the amount of values captured
+        }).ok_or(LTC2983Error::NoValidSamples)
+    }
+
+    ///do a separate number of conversions for every channel then calculate the average of the temperatures read out
+    ///
+    ///All channels that still need readings are converted together in one multi channel conversion,
+    ///a channel drops out of the sweeps once it has its `rounds` valid readings. An invalid or suspect
+    ///reading is not counted and the channel is converted again in the next sweep, at most `rounds`
+    ///readings of a channel are retried before [`LTC2983Error::SamplesRejected`] is returned.
+    pub fn get_multi_temperature_avg_rounds(&mut self, channels: &[(LTC2983Channel, usize)]) -> Result<Vec<(LTC2983Channel, f32)>, LTC2983Error<SPI::Error>> {
+        let mut sums = vec![0.0f32; channels.len()];
+        let mut remaining: Vec<usize> = channels.iter().map(|(_, rounds)| *rounds).collect();
+        let mut rejected = vec![0usize; channels.len()];
+
+        loop {
+            let pending: Vec<usize> = (0..channels.len()).filter(|&i| remaining[i] > 0).collect();
+            if pending.is_empty() {
+                break;
+            }
+            let sweep: Vec<LTC2983Channel> = pending.iter().map(|&i| channels[i].0).collect();
+
+            self.start_multi_conversion(&sweep)?;
+            self.wait_for_conversion()?;
+            for (i, (_, res)) in pending.into_iter().zip(self.read_multi_temperature(&sweep)) {
+                match res? {
+                    LTC2983Result::Valid(temp) => {
+                        sums[i] += temp.value();
+                        remaining[i] -= 1;
+                    }
+                    LTC2983Result::Invalid(_) | LTC2983Result::Suspect(_, _) => {
+                        rejected[i] += 1;
+                        if rejected[i] > channels[i].1 { // every reading got its retry, the channel does not deliver valid ones
+                            return Err(LTC2983Error::SamplesRejected(rejected[i], channels[i].1));
+                        }
+                    }
+                }
+            }
+        }
+
+        channels.iter().zip(sums).map(|(&(channel, rounds), sum)| {
+            if rounds == 0 {
+                return Err(LTC2983Error::NoValidSamples);
+            }
+            Ok((channel, sum / rounds as f32))
+        }).collect()
+    }
+}
+
+fn mean(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f32>() / values.len() as f32)
+    }
+}
+
+//keep the values within `sigma` sample standard deviations of the mean
+fn reject_outliers(values: &[f32], sigma: f32) -> Vec<f32> {
+    let Some(stats) = TemperatureStats::from_samples(&Samples { values: values.to_vec(), rejected: 0 }) else {
+        return Vec::new();
+    };
+    values.iter().copied().filter(|v| (v - stats.mean).abs() <= sigma * stats.std_dev).collect()
+}
+
+//check that `len` bytes starting at `address` lie within the register space
+fn check_register_range<E>(address: u16, len: usize) -> Result<(), LTC2983Error<E>> {
+    if address as usize + len > LAST_REGISTER as usize + 1 {
+        return Err(LTC2983Error::RegisterOutOfRange(address, len));
+    }
+    Ok(())
+}
+
+//decode a result word according to the sensor configured on the channel, temperatures are converted to °C
+fn decode_result(bytes: [u8; 4], probe: Option<&ThermalProbeType>, global_configuration: &GlobalConfiguration) -> LTC2983Result {
+    let unit = global_configuration.temperature_unit();
+    match probe {
+        Some(ThermalProbeType::DirectADC(_)) => LTC2983Result::from_voltage_bytes(bytes),
+        Some(ThermalProbeType::SenseResistor(_)) => LTC2983Result::decode(bytes, |bytes| {
+            Reading::Resistance(Ohms(resistance_from_result_bytes(bytes).to_num()))
+        }),
+        Some(ThermalProbeType::Diode(param)) => LTC2983Result::decode(bytes, |bytes| {
+            Reading::Temperature(Celsius(unit.to_celsius(fixed_from_result_bytes(bytes).to_num()) + param.offset_c))
+        }),
+        _ => LTC2983Result::decode(bytes, |bytes| {
+            Reading::Temperature(Celsius(unit.to_celsius(fixed_from_result_bytes(bytes).to_num())))
+        }),
+    }
+}
+
+//the temperature of a result word in °C like decode_result, computed in fixed point
+fn fixed_celsius_from_result_bytes(bytes: &[u8; 4], probe: Option<&ThermalProbeType>, unit: TemperatureUnit) -> FixedI32<U10> {
+    let value = fixed_from_result_bytes(bytes);
+    let celsius = match unit {
+        TemperatureUnit::Celsius => value,
+        TemperatureUnit::Fahrenheit => (value - FixedI32::<U10>::from_num(32)) * 5 / 9,
+        TemperatureUnit::Kelvin => value - FixedI32::<U10>::from_bits(279_706), // 273.15 K
+    };
+    match probe {
+        Some(ThermalProbeType::Diode(param)) => celsius + FixedI32::<U10>::from_num(param.offset_c),
+        _ => celsius
+    }
+}
+
+//check that the channels `probe` refers to are configured suitably, `configured` looks up the sensor of a channel
+fn validate_references<'a>(probe: &ThermalProbeType,
+                           configured: impl Fn(LTC2983Channel) -> Option<&'a ThermalProbeType>) -> Result<(), ConfigError>
+{
+    if let Some(cold_junction) = probe.thermocouple_parameters().and_then(|param| param.cold_junction_channel) {
+        if !configured(cold_junction).is_some_and(ThermalProbeType::can_be_cold_junction) {
+            return Err(ConfigError::ColdJunctionUnconfigured(cold_junction));
+        }
+    }
+    if let Some(r_sense) = probe.r_sense_channel() {
+        if !matches!(configured(r_sense), Some(ThermalProbeType::SenseResistor(_))) {
+            return Err(ConfigError::SenseResistorUnconfigured(r_sense));
+        }
+    }
+    Ok(())
+}
+
+//hundredths of a degree, PACKED_FAULT if the value does not fit
+fn pack_hundredths(celsius: f32) -> i16 {
+    let hundredths = (celsius * 100.).round();
+    if hundredths.abs() <= i16::MAX as f32 {
+        hundredths as i16
+    } else {
+        PACKED_FAULT
+    }
+}
+
+//buffer to pack a configuration word into, the fields are written MSB first and the word is read
+//back big-endian, the byte order the device expects on the bus
+fn word_buffer() -> ByteBuffer {
+    let mut buffer = ByteBuffer::new();
+    buffer.set_endian(Endian::BigEndian);
+    buffer
+}
+
+//the upper 5 bits of a channel configuration word hold the sensor type, zero means the channel is disabled
+fn sensor_type_assigned(config_msb: u8) -> bool {
+    config_msb & 0xf8 != 0
+}
+
+//encode a delay in µs into the 100µs steps of the mux configuration delay register
+fn mux_delay_bits(delay_us: u32) -> u8 {
+    delay_us.div_ceil(100).min(u8::MAX as u32) as u8
+}
+
+//voltage drop in volts across `resistance` ohms driven with `microamps`
+fn expected_sense_voltage(resistance: f32, microamps: f32) -> f32 {
+    resistance * microamps * 1e-6
+}
+
+//extract the signed fixed point value (U10) from a 32 bit result word
+fn fixed_from_result_bytes(bytes: &[u8; 4]) -> FixedI32<U10> {
+    FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(bytes[1..=3].try_into().unwrap()))
+}
+
+//resistances are unsigned 24 bit values with 6 fractional bits (1/64 Ω), the resistance format of
+//the custom RTD tables, covering the 0 to 131072 Ω of a sense resistor
+fn resistance_from_result_bytes(bytes: &[u8; 4]) -> FixedU32<U6> {
+    FixedU32::<U6>::from_be_bytes([0x00, bytes[1], bytes[2], bytes[3]])
+}
+
+//average fixed point values using a wider accumulator so the sum can not overflow
+fn average_fixed(values: &[FixedI32<U10>]) -> Option<FixedI32<U10>> {
+    if values.is_empty() {
+        return None;
+    }
+    let sum = values.iter().fold(FixedI64::<U10>::ZERO, |acc, v| acc + FixedI64::<U10>::from_num(*v));
+    Some(FixedI32::<U10>::from_num(sum / values.len() as i64))
+}
+
+fn reformat_fixedf24_to_fixed_f32(bytes_f24: &[u8; 3]) -> [u8; 4]{
+    if bytes_f24[0] & 0x80 == 0x80 {
+        [0xff, bytes_f24[0], bytes_f24[1], bytes_f24[2]]
+    } else {
+        [0x00, bytes_f24[0], bytes_f24[1], bytes_f24[2]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::{ErrorKind, ErrorType, Operation};
+    use fixed::{FixedI32, types::extra::U10};
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct FakeSpiError;
+
+    /// Bus and chip select pin recording their activity into a shared log.
+    struct FakeBus(std::rc::Rc<std::cell::RefCell<Vec<String>>>);
+    struct FakePin(&'static str, std::rc::Rc<std::cell::RefCell<Vec<String>>>);
+
+    /// Reads low for the given number of samples, high afterwards.
+    struct FakeInterrupt(usize);
+
+    impl ErrorType for FakeBus {
+        type Error = FakeSpiError;
+    }
+
+    impl SpiBus for FakeBus {
+        fn read(&mut self, _: &mut [u8]) -> Result<(), FakeSpiError> {
+            self.0.borrow_mut().push("read".into());
+            Ok(())
+        }
+
+        fn write(&mut self, words: &[u8]) -> Result<(), FakeSpiError> {
+            self.0.borrow_mut().push(format!("write {words:x?}"));
+            Ok(())
+        }
+
+        fn transfer(&mut self, _: &mut [u8], write: &[u8]) -> Result<(), FakeSpiError> {
+            self.0.borrow_mut().push(format!("transfer {write:x?}"));
+            Ok(())
+        }
+
+        fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), FakeSpiError> {
+            self.0.borrow_mut().push(format!("transfer {words:x?}"));
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), FakeSpiError> {
+            self.0.borrow_mut().push("flush".into());
+            Ok(())
+        }
+    }
+
+    impl embedded_hal::digital::ErrorType for FakePin {
+        type Error = std::convert::Infallible;
+    }
+
+    impl OutputPin for FakePin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.1.borrow_mut().push(format!("{} low", self.0));
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.1.borrow_mut().push(format!("{} high", self.0));
+            Ok(())
+        }
+    }
+
+    impl embedded_hal::digital::ErrorType for FakeInterrupt {
+        type Error = std::convert::Infallible;
+    }
+
+    impl InputPin for FakeInterrupt {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.is_low()?)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            let low = self.0 > 0;
+            self.0 = self.0.saturating_sub(1);
+            Ok(low)
+        }
+    }
+
+    impl embedded_hal::spi::Error for FakeSpiError {
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    /// Register level model of the device, conversions finish after `busy_polls` status reads.
+    struct FakeChip {
+        mem: Vec<u8>,
+        busy_polls: usize,
+        remaining_polls: usize,
+        slow_channel: Option<(LTC2983Channel, usize)>,
+        //start commands written while a conversion was still running
+        busy_starts: usize,
+        sequences: Vec<(LTC2983Channel, Vec<[u8; 4]>, usize)>,
+        writes: Vec<Vec<u8>>,
+        //every frame in the order it was received, reads included
+        frames: Vec<Vec<u8>>,
+        failures: usize,
+        failing_read: Option<u16>,
+    }
+
+    impl FakeChip {
+        fn new() -> Self {
+            let mut mem = vec![0; 0x1000];
+            mem[STATUS_REGISTER as usize] = 0x40; // idle and done after power up
+            Self { mem, busy_polls: 0, remaining_polls: 0, slow_channel: None, busy_starts: 0, sequences: Vec::new(), writes: Vec::new(), frames: Vec::new(), failures: 0, failing_read: None }
+        }
+
+        /// Every conversion of `channel` reports the next of `results`, starting over after the last.
+        fn result_sequence(mut self, channel: LTC2983Channel, results: &[[u8; 4]]) -> Self {
+            self.sequences.push((channel, results.to_vec(), 0));
+            self
+        }
+
+        fn start_conversion(&mut self, selection: u8) {
+            self.remaining_polls = match self.slow_channel {
+                Some((channel, polls)) if channel.identifier() as u8 == selection => polls,
+                _ => self.busy_polls,
+            };
+            let mask = u32::from_be_bytes(self.mem[0x0F4..0x0F8].try_into().unwrap());
+            for i in 0..self.sequences.len() {
+                let (channel, ref results, next) = self.sequences[i];
+                let converted = selection == channel.identifier() as u8 || (selection == 0 && mask & channel.mask() != 0);
+                if converted {
+                    let result = results[next % results.len()];
+                    self.sequences[i].2 += 1;
+                    self.set_result(channel, result);
+                }
+            }
+        }
+
+        /// The next `failures` transactions fail without reaching the chip.
+        fn failing_transactions(mut self, failures: usize) -> Self {
+            self.failures = failures;
+            self
+        }
+
+        /// Every transaction reading from `address` fails without reaching the chip.
+        fn failing_reads_of(mut self, address: u16) -> Self {
+            self.failing_read = Some(address);
+            self
+        }
+
+        /// Conversions of `channel` never finish.
+        fn stuck_channel(self, channel: LTC2983Channel) -> Self {
+            self.slow_channel(channel, usize::MAX)
+        }
+
+        /// Conversions of `channel` take `polls` status reads instead of the usual `busy_polls`.
+        fn slow_channel(mut self, channel: LTC2983Channel, polls: usize) -> Self {
+            self.slow_channel = Some((channel, polls));
+            self
+        }
+
+        fn busy_polls(mut self, polls: usize) -> Self {
+            self.busy_polls = polls;
+            self
+        }
+
+        fn set_result(&mut self, channel: LTC2983Channel, word: [u8; 4]) {
+            let addr = channel.result_address() as usize;
+            self.mem[addr..addr + 4].copy_from_slice(&word);
+        }
+
+        fn frame(&mut self, write: &[u8], read: Option<&mut [u8]>) {
+            let addr = u16::from_be_bytes([write[1], write[2]]) as usize;
+            let data = &write[3..];
+            self.frames.push(write[..3].to_vec());
+            match write[0] {
+                LTC2983_WRITE => {
+                    self.writes.push(write.to_vec());
+                    if addr == STATUS_REGISTER as usize && self.mem[addr] & 0x40 == 0 {
+                        self.busy_starts += 1;
+                    }
+                    self.mem[addr..addr + data.len()].copy_from_slice(data);
+                    if addr == STATUS_REGISTER as usize && data[0] & 0x80 != 0 {
+                        self.start_conversion(data[0] & 0x1f);
+                    }
+                }
+                LTC2983_READ => {
+                    if addr == STATUS_REGISTER as usize && self.mem[addr] & 0x80 != 0 {
+                        if self.remaining_polls == 0 {
+                            self.mem[addr] = 0x40 | (self.mem[addr] & 0x1f);
+                        } else {
+                            self.remaining_polls -= 1;
+                        }
+                    }
+                    if let Some(read) = read {
+                        let len = read.len() - 3;
+                        read[3..].copy_from_slice(&self.mem[addr..addr + len]);
+                    }
+                }
+                _ => panic!("unknown instruction {:#x}", write[0]),
+            }
+        }
+    }
+
+    impl ErrorType for FakeChip {
+        type Error = FakeSpiError;
+    }
+
+    impl SpiDevice for FakeChip {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), FakeSpiError> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(FakeSpiError);
+            }
+            let reads_failing_address = operations.iter().any(|op| match op {
+                Operation::Write(write) | Operation::Transfer(_, write) => write[0] == LTC2983_READ && Some(u16::from_be_bytes([write[1], write[2]])) == self.failing_read,
+                Operation::TransferInPlace(buf) => buf[0] == LTC2983_READ && Some(u16::from_be_bytes([buf[1], buf[2]])) == self.failing_read,
+                Operation::Read(_) | Operation::DelayNs(_) => false
+            });
+            if reads_failing_address {
+                return Err(FakeSpiError);
+            }
+            for op in operations {
+                match op {
+                    Operation::Write(write) => self.frame(write, None),
+                    Operation::Transfer(read, write) => self.frame(write, Some(read)),
+                    Operation::TransferInPlace(buf) => {
+                        let write = buf.to_vec();
+                        self.frame(&write, Some(buf))
+                    }
+                    Operation::Read(_) | Operation::DelayNs(_) => {}
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Let the driver take `channels` as configured diodes without writing the configuration.
+    fn assume_diodes<W: ConversionWaiter>(ltc: &mut LTC2983<FakeChip, W>, channels: &[LTC2983Channel]) {
+        for channel in channels {
+            ltc.channels[channel.index()] = Some(ThermalProbeType::Diode(DiodeParameters::default()));
+        }
+    }
+
+    /// Clock that only moves when the test advances it or by `step` on every reading, clones share the time.
+    #[derive(Default, Clone)]
+    struct FakeClock(Arc<Mutex<Duration>>, Duration);
+
+    impl FakeClock {
+        fn ticking(step: Duration) -> Self {
+            FakeClock(Default::default(), step)
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Duration {
+            let mut now = self.0.lock().unwrap();
+            let read = *now;
+            *now += self.1;
+            read
+        }
+    }
+
+    /// Delay that only keeps track of the requested time.
+    #[derive(Default)]
+    struct FakeDelay {
+        waited_ns: u64,
+    }
+
+    impl DelayNs for &mut FakeDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.waited_ns += ns as u64;
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::spi::SpiDevice for FakeChip {
+        async fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), FakeSpiError> {
+            SpiDevice::transaction(self, operations)
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::delay::DelayNs for &mut FakeDelay {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.waited_ns += ns as u64;
+        }
+    }
+
+    //drive a future that never has to wait on anything external to completion
+    #[cfg(feature = "async")]
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        loop {
+            if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixedf24_u10_to_f32_signed() {
+        let bytes: [u8; 3] = [ 0x7f, 0xff, 0xff ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (8191.999_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0x10, 0x00, 0x00 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (1024_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0x00, 0x04, 0x00 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (1_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0x00, 0x00, 0x01 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (1./1024_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0x00, 0x00, 0x00 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (0_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0xff, 0xff, 0xff ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (-1./1024_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0xff, 0xfc, 0x00 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (-1_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0xfb, 0xbb, 0x67 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (-273.15_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        // positive and negative full scale
+        let bytes: [u8; 3] = [ 0x7f, 0xfc, 0x00 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (8191_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0x80, 0x00, 0x00 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (-8192_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0x80, 0x00, 0x01 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (-8191.999_f32)).abs() < 1./1024.); // error should be smaller than smallest fixed point value 1./1024.
+
+        let bytes: [u8; 3] = [ 0xf8, 0xd1, 0x52 ];
+        let value = FixedI32::<U10>::from_be_bytes(reformat_fixedf24_to_fixed_f32(&bytes));
+        assert!((value.to_num::<f32>() - (-459.67_f32)).abs() < 1./1027.); // error should be smaller than smallest fixed point value 1./1024.
+    }
+
+    #[test]
+    fn test_poll_waiter() {
+        let mut chip = FakeChip::new().busy_polls(5);
+        chip.set_result(LTC2983Channel::CH3, [0x01, 0x00, 0x64, 0x66]);
+        let mut ltc = LTC2983::with_waiter(chip, PollWaiter::new(10));
+        assert!(matches!(ltc.measure(LTC2983Channel::CH3).map(|m| m.result), Ok(LTC2983Result::Valid(_))));
+
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().busy_polls(20), PollWaiter::new(10));
+        assert!(matches!(ltc.measure(LTC2983Channel::CH3), Err(LTC2983Error::ConversionTimeout)));
+    }
+
+    #[test]
+    fn test_delay_waiter() {
+        let mut delay = FakeDelay::default();
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().busy_polls(3), DelayWaiter::new(&mut delay, 1_000, 10_000));
+        assert!(ltc.measure(LTC2983Channel::CH1).is_ok());
+        drop(ltc);
+        assert_eq!(delay.waited_ns, 3_000_000);
+
+        let mut delay = FakeDelay::default();
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().busy_polls(100), DelayWaiter::new(&mut delay, 1_000, 10_000));
+        assert!(matches!(ltc.measure(LTC2983Channel::CH1), Err(LTC2983Error::ConversionTimeout)));
+        drop(ltc);
+        assert_eq!(delay.waited_ns, 10_000_000);
+
+        //without an interval every poll counts as 1µs, a stuck conversion still times out
+        let mut delay = FakeDelay::default();
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().stuck_channel(LTC2983Channel::CH1), DelayWaiter::new(&mut delay, 0, 50));
+        assert!(matches!(ltc.measure(LTC2983Channel::CH1), Err(LTC2983Error::ConversionTimeout)));
+        let mut delay = FakeDelay::default();
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().stuck_channel(LTC2983Channel::CH1),
+                                           InterruptWaiter::new(FakeInterrupt(usize::MAX), &mut delay, 0, 50));
+        assert!(matches!(ltc.measure(LTC2983Channel::CH1), Err(LTC2983Error::ConversionTimeout)));
+    }
+
+    #[test]
+    fn test_conversion_state() {
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(2));
+        assert_eq!(ltc.conversion_state(), ConversionState::Idle);
+        assert!(matches!(ltc.poll_done(), Err(LTC2983Error::NoConversionRunning)));
+
+        ltc.start_conversion(LTC2983Channel::CH4).unwrap();
+        assert_eq!(ltc.conversion_state(), ConversionState::Converting);
+        assert!(!ltc.poll_done().unwrap());
+        assert!(!ltc.poll_done().unwrap());
+        assert!(ltc.poll_done().unwrap());
+        assert_eq!(ltc.conversion_state(), ConversionState::Done);
+
+        ltc.read_temperature(LTC2983Channel::CH4).unwrap();
+        assert_eq!(ltc.conversion_state(), ConversionState::Idle);
+    }
+
+    #[test]
+    fn test_sense_voltage() {
+        assert!((expected_sense_voltage(2000., RTDExcitationCurrent::I250uA.microamps()) - 0.5).abs() < 1e-6);
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(matches!(ltc.read_sense_voltage(LTC2983Channel::CH2), Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistorUnconfigured(LTC2983Channel::CH2)))));
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        assert!(matches!(ltc.read_sense_voltage(LTC2983Channel::CH2), Err(LTC2983Error::SenseResistorUnused(LTC2983Channel::CH2))));
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default().channel(LTC2983Channel::CH2).excitation_current(RTDExcitationCurrent::I100uA)), LTC2983Channel::CH4).unwrap();
+
+        let voltage = ltc.read_sense_voltage(LTC2983Channel::CH2).unwrap();
+        assert!((voltage.expected - 0.2).abs() < 1e-6);
+        assert_eq!(voltage.measured, None);
+
+        ltc.spi_device.set_result(LTC2983Channel::CH2, [0x01, 0x01, 0xf5, 0x00]); // 2004 Ω
+        let voltage = ltc.read_sense_voltage(LTC2983Channel::CH2).unwrap();
+        assert!((voltage.measured.unwrap() - 0.2004).abs() < 1e-6);
+
+        //a thermistor uses the sense resistor just as well
+        ltc.setup_channel(ThermalProbeType::SenseResistor(10000.), LTC2983Channel::CH6).unwrap();
+        ltc.setup_channel(ThermalProbeType::Thermistor_44006_44031(ThermistorParameters::default()
+            .channel(LTC2983Channel::CH6)
+            .excitation_current(ThermistorExcitationCurrent::I10uA)), LTC2983Channel::CH8).unwrap();
+        assert!((ltc.read_sense_voltage(LTC2983Channel::CH6).unwrap().expected - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reading_follows_channel_kind() {
+        let mut chip = FakeChip::new();
+        chip.set_result(LTC2983Channel::CH1, [0x01, 0x00, 0x64, 0x66]);
+        chip.set_result(LTC2983Channel::CH2, [0x01, 0x10, 0x00, 0x00]);
+        let mut ltc = LTC2983::new(chip);
+        ltc.setup_channel(ThermalProbeType::DirectADC(DirectADCParameters::default()), LTC2983Channel::CH2).unwrap();
+
+        match ltc.read_temperature(LTC2983Channel::CH1).unwrap() {
+            LTC2983Result::Valid(Reading::Temperature(Celsius(t))) => assert!((t - 25.1).abs() < 1./1024.),
+            r => panic!("unexpected result {r:?}"),
+        }
+        match ltc.read_temperature(LTC2983Channel::CH2).unwrap() {
+            LTC2983Result::Valid(Reading::Voltage(Volts(v))) => assert!((v - 0.5).abs() < 1e-6),
+            r => panic!("unexpected result {r:?}"),
+        }
+
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH4).unwrap();
+        ltc.spi_device.set_result(LTC2983Channel::CH4, [0x01, 0x01, 0xf4, 0x00]); // 2000 * 64
+        let result = ltc.read_temperature(LTC2983Channel::CH4).unwrap();
+        assert!(matches!(result, LTC2983Result::Valid(Reading::Resistance(Ohms(r))) if r == 2000.));
+        assert_eq!(result.to_string(), "2000 Ω");
+        assert!(matches!(ltc.read_temperature_as(LTC2983Channel::CH4, TemperatureUnit::Celsius), Err(LTC2983Error::NotATemperature(LTC2983Channel::CH4))));
+        assert_eq!(ltc.read_resistance(LTC2983Channel::CH4).unwrap(), Ohms(2000.));
+        //the 10kΩ sense resistor of a thermistor is beyond the 8192 Ω 1/1024 Ω steps could hold
+        ltc.spi_device.set_result(LTC2983Channel::CH4, [0x01, 0x09, 0xc4, 0x20]); // 10000.5 * 64
+        assert_eq!(ltc.read_resistance(LTC2983Channel::CH4).unwrap(), Ohms(10000.5));
+        ltc.spi_device.set_result(LTC2983Channel::CH4, [0x81, 0x00, 0x00, 0x00]);
+        assert!(matches!(ltc.read_resistance(LTC2983Channel::CH4), Err(LTC2983Error::FaultyResult(_))));
+        assert!(matches!(ltc.read_resistance(LTC2983Channel::CH2),
+                         Err(LTC2983Error::SensorKindMismatch { expected: SensorKind::SenseResistor, actual: SensorKind::DirectADC })));
+        assert!(matches!(ltc.read_voltage(LTC2983Channel::CH2), Ok(Volts(v)) if (v - 0.5).abs() < 1e-6));
+        assert!(matches!(ltc.read_voltage(LTC2983Channel::CH4),
+                         Err(LTC2983Error::SensorKindMismatch { expected: SensorKind::DirectADC, actual: SensorKind::SenseResistor })));
+        assert!(matches!(ltc.read_voltage(LTC2983Channel::CH9), Err(LTC2983Error::ChannelUnconfigured(LTC2983Channel::CH9))));
+    }
+
+    #[test]
+    fn test_fault_flags() {
+        match LTC2983Result::from([0x81, 0x00, 0x00, 0x00]) {
+            LTC2983Result::Invalid(faults) => {
+                assert!(faults.contains(FaultFlags::SENSOR_HARD_FAULT));
+                assert!(faults.hard_fault());
+            }
+            r => panic!("unexpected result {r:?}"),
+        }
+        match LTC2983Result::from([0x11, 0x00, 0x64, 0x66]) {
+            LTC2983Result::Suspect(_, faults) => assert_eq!(faults, FaultFlags::CJ_SOFT_FAULT),
+            r => panic!("unexpected result {r:?}"),
+        }
+        assert_eq!(LTC2983Result::from([0x01, 0x00, 0x64, 0x66]).faults(), FaultFlags::NONE);
+    }
+
+    #[test]
+    fn test_new_fault_fires_once() {
+        let mut chip = FakeChip::new();
+        chip.set_result(LTC2983Channel::CH5, [0x81, 0x00, 0x00, 0x00]);
+        let mut ltc = LTC2983::new(chip);
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let log = reported.clone();
+        ltc.on_new_fault(move |channel, faults| log.lock().unwrap().push((channel, faults)));
+
+        for _ in 0..3 {
+            ltc.measure(LTC2983Channel::CH5).unwrap();
+        }
+        assert_eq!(*reported.lock().unwrap(), vec![(LTC2983Channel::CH5, FaultFlags::SENSOR_HARD_FAULT)]);
+    }
+
+    #[test]
+    fn test_scan_with_deadline_returns_partial_results() {
+        let starts = |ltc: &LTC2983<FakeChip>| ltc.spi_device.writes.iter().filter(|w| w[..3] == [LTC2983_WRITE, 0x00, 0x00]).count();
+        let channels = [LTC2983Channel::CH1, LTC2983Channel::CH2, LTC2983Channel::CH3];
+
+        //every clock reading takes 1ms, each channel gets a share of about 20ms
+        let chip = FakeChip::new().busy_polls(2).stuck_channel(LTC2983Channel::CH2)
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x66]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0xcc]]);
+        let mut ltc = LTC2983::new(chip);
+        ltc.set_clock(FakeClock::ticking(Duration::from_millis(1)));
+        let results = ltc.scan_all_with_deadline(&channels, Duration::from_millis(60)).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], (LTC2983Channel::CH1, ScanOutcome::Completed(LTC2983Result::Valid(_)))));
+        assert!(matches!(results[1], (LTC2983Channel::CH2, ScanOutcome::Timeout)));
+        //the device never got done with CH2, so CH3 is not started
+        assert!(matches!(results[2], (LTC2983Channel::CH3, ScanOutcome::Timeout)));
+        assert_eq!(starts(&ltc), 2);
+
+        //CH2 misses its share but finishes in time for CH3
+        let chip = FakeChip::new().busy_polls(2).slow_channel(LTC2983Channel::CH2, 30)
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x66]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0xcc]]);
+        let mut ltc = LTC2983::new(chip);
+        ltc.set_clock(FakeClock::ticking(Duration::from_millis(1)));
+        let results = ltc.scan_all_with_deadline(&channels, Duration::from_millis(60)).unwrap();
+        assert!(matches!(results[1], (LTC2983Channel::CH2, ScanOutcome::Timeout)));
+        match results[2] {
+            (LTC2983Channel::CH3, ScanOutcome::Completed(LTC2983Result::Valid(r))) => assert!((r.value() - 25.2).abs() < 1e-3),
+            ref r => panic!("unexpected result {r:?}"),
+        }
+        assert_eq!(ltc.spi_device.busy_starts, 0);
+    }
+
+    #[test]
+    fn test_mux_delay() {
+        assert_eq!(mux_delay_bits(0), 0);
+        assert_eq!(mux_delay_bits(100), 1);
+        assert_eq!(mux_delay_bits(250), 3);
+        assert_eq!(mux_delay_bits(25_500), 255);
+        assert_eq!(mux_delay_bits(1_000_000), 255);
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.set_mux_delay(1_000).unwrap();
+        assert_eq!(ltc.spi_device.writes, vec![vec![LTC2983_WRITE, 0x00, 0xff, 10]]);
+    }
+
+    #[test]
+    fn test_status_accessors() {
+        let status = LTC2983Status::from(0x87);
+        assert!(status.started());
+        assert!(!status.done());
+        assert_eq!(status.active_channel(), Some(LTC2983Channel::CH7));
+
+        let status = LTC2983Status::from(0x54);
+        assert!(!status.started());
+        assert!(status.done());
+        assert_eq!(status.active_channel(), Some(LTC2983Channel::CH20));
+
+        assert_eq!(LTC2983Status::from(0x80).active_channel(), None);
+    }
+
+    #[test]
+    fn test_excitation_current_microamps() {
+        let rtd = [
+            (RTDExcitationCurrent::External, 0.),
+            (RTDExcitationCurrent::I5uA, 5.),
+            (RTDExcitationCurrent::I10uA, 10.),
+            (RTDExcitationCurrent::I25uA, 25.),
+            (RTDExcitationCurrent::I50uA, 50.),
+            (RTDExcitationCurrent::I100uA, 100.),
+            (RTDExcitationCurrent::I250uA, 250.),
+            (RTDExcitationCurrent::I500uA, 500.),
+            (RTDExcitationCurrent::I1mA, 1000.),
+        ];
+        for (current, microamps) in rtd {
+            assert_eq!(current.microamps(), microamps);
+        }
+
+        let diode = [
+            (DiodeExcitationCurrent::I10uA, 10.),
+            (DiodeExcitationCurrent::I20uA, 20.),
+            (DiodeExcitationCurrent::I40uA, 40.),
+            (DiodeExcitationCurrent::I80uA, 80.),
+        ];
+        for (current, microamps) in diode {
+            assert_eq!(current.microamps(), microamps);
+        }
+    }
+
+    #[test]
+    fn test_read_temperature_as() {
+        let mut chip = FakeChip::new();
+        chip.set_result(LTC2983Channel::CH1, [0x01, 0x00, 0x64, 0x66]); // 25.1
+        chip.set_result(LTC2983Channel::CH2, [0x81, 0x00, 0x00, 0x00]);
+        let mut ltc = LTC2983::new(chip);
+
+        let kelvin = ltc.read_temperature_as(LTC2983Channel::CH1, TemperatureUnit::Kelvin).unwrap();
+        assert!((kelvin - 298.25).abs() < 1e-2);
+        let fahrenheit = ltc.read_temperature_as(LTC2983Channel::CH1, TemperatureUnit::Fahrenheit).unwrap();
+        assert!((fahrenheit - 77.18).abs() < 1e-2);
+        assert!(matches!(ltc.read_temperature_as(LTC2983Channel::CH2, TemperatureUnit::Kelvin), Err(LTC2983Error::FaultyResult(_))));
+
+        // device reports 77.18 °F
+        ltc.set_global_configuration(GlobalConfiguration::default().fahrenheit(true)).unwrap();
+        ltc.spi_device.set_result(LTC2983Channel::CH1, [0x01, 0x01, 0x34, 0xb8]);
+        let celsius = ltc.read_temperature_as(LTC2983Channel::CH1, TemperatureUnit::Celsius).unwrap();
+        assert!((celsius - 25.1).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_average_reports_rejected_samples() {
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH1, &[
+            [0x01, 0x00, 0x64, 0x00], // 25.0
+            [0x81, 0x00, 0x00, 0x00],
+            [0x01, 0x00, 0x68, 0x00], // 26.0
+            [0x09, 0x00, 0x68, 0x00],
+        ]));
+        let avg = ltc.get_temperature_avg_detailed(LTC2983Channel::CH1, 4).unwrap();
+        assert_eq!(avg, TemperatureAverage { average: 25.5, valid_samples: 2, rejected: 2 });
+
+        assert!(matches!(ltc.get_temperature_avg(LTC2983Channel::CH1, 4), Err(LTC2983Error::SamplesRejected(2, 4))));
+        assert!(matches!(ltc.get_temperature_avg(LTC2983Channel::CH1, 0), Err(LTC2983Error::NoValidSamples)));
+
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().stuck_channel(LTC2983Channel::CH1), PollWaiter::new(5));
+        assert!(matches!(ltc.get_temperature_avg(LTC2983Channel::CH1, 4), Err(LTC2983Error::ConversionTimeout)));
+
+        //a failed read of the result is an error, not a rejected reading
+        let mut ltc = LTC2983::new(FakeChip::new().failing_reads_of(LTC2983Channel::CH1.result_address()));
+        assert!(matches!(ltc.get_temperature_avg_detailed(LTC2983Channel::CH1, 4), Err(LTC2983Error::SpiError(_))));
+    }
+
+    #[test]
+    fn test_temperature_stats() {
+        let samples = Samples { values: vec![21., 25., 23., 22., 24.], rejected: 1 };
+        let stats = TemperatureStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.mean, 23.);
+        assert_eq!(stats.median, 23.);
+        assert_eq!(stats.min, 21.);
+        assert_eq!(stats.max, 25.);
+        assert!((stats.std_dev - 2.5_f32.sqrt()).abs() < 1e-6);
+        assert_eq!(stats.valid_samples, 5);
+        assert_eq!(stats.rejected, 1);
+
+        let samples = Samples { values: vec![20., 21., 23., 22.], rejected: 0 };
+        assert_eq!(TemperatureStats::from_samples(&samples).unwrap().median, 21.5);
+        assert_eq!(TemperatureStats::from_samples(&Samples { values: vec![], rejected: 3 }), None);
+
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH1, &[
+            [0x01, 0x00, 0x64, 0x00], // 25.0
+            [0x01, 0x00, 0x68, 0x00], // 26.0
+        ]));
+        let stats = ltc.get_temperature_stats(LTC2983Channel::CH1, 2).unwrap();
+        assert_eq!((stats.min, stats.max, stats.mean), (25., 26., 25.5));
+    }
+
+    #[test]
+    fn test_topology_dot() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH1).unwrap();
+        ltc.setup_channel(ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH1)), LTC2983Channel::CH3).unwrap();
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH5).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default().channel(LTC2983Channel::CH5)), LTC2983Channel::CH7).unwrap();
+        ltc.setup_channel(ThermalProbeType::Thermistor_44006_44031(ThermistorParameters::default().channel(LTC2983Channel::CH5)), LTC2983Channel::CH9).unwrap();
+
+        let dot = ltc.topology_dot();
+        assert!(dot.starts_with("digraph ltc2983 {\n"));
+        assert!(dot.contains("    CH3 [label=\"CH3\\nThermocouple K\"];\n"));
+        assert!(dot.contains("    CH3 -> CH1 [label=\"cold junction\"];\n"));
+        assert!(dot.contains("    CH7 -> CH5 [label=\"sense resistor\"];\n"));
+        assert!(dot.contains("    CH9 -> CH5 [label=\"sense resistor\"];\n"));
+        assert_eq!(dot.matches("->").count(), 3);
+    }
+
+    #[test]
+    fn test_outlier_rejection() {
+        let values = [25.0, 25.1, 24.9, 25.0, 25.1, 24.9, 25.0, 80.0];
+        let kept = reject_outliers(&values, 2.);
+        assert_eq!(kept.len(), 7);
+        assert!(!kept.contains(&80.0));
+
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH1, &[
+            [0x01, 0x00, 0x64, 0x00], // 25.0
+            [0x01, 0x00, 0x64, 0x00],
+            [0x01, 0x00, 0x64, 0x00],
+            [0x01, 0x00, 0x64, 0x00],
+            [0x01, 0x01, 0x40, 0x00], // 80.0
+        ]));
+        assert_eq!(ltc.get_temperature_avg_filtered(LTC2983Channel::CH1, 5, 1.5).unwrap(), 25.0);
+
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH1, &[
+            [0x01, 0x00, 0x64, 0x00],
+            [0x81, 0x00, 0x00, 0x00],
+        ]));
+        assert!(matches!(ltc.get_temperature_avg_filtered(LTC2983Channel::CH1, 4, 2.), Err(LTC2983Error::TooFewSamples(2))));
+    }
+
+    #[test]
+    fn test_measurement_timestamps() {
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(1));
+        let before = ltc.now();
+        let first = ltc.measure(LTC2983Channel::CH1).unwrap();
+        let second = ltc.measure(LTC2983Channel::CH2).unwrap();
+        assert_eq!((first.channel, second.channel), (LTC2983Channel::CH1, LTC2983Channel::CH2));
+        assert!(before <= first.timestamp);
+        assert!(first.timestamp <= second.timestamp);
+
+        let clock = FakeClock::default();
+        ltc.set_clock(clock.clone());
+        clock.advance(Duration::from_millis(3));
+        assert_eq!(ltc.measure(LTC2983Channel::CH1).unwrap().timestamp, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_busy_policy() {
+        let starts = |ltc: &LTC2983<FakeChip>| ltc.spi_device.writes.iter().filter(|w| w[..3] == [LTC2983_WRITE, 0x00, 0x00]).count();
+
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(100));
+        ltc.start_conversion(LTC2983Channel::CH1).unwrap();
+        ltc.start_conversion(LTC2983Channel::CH2).unwrap();
+        assert_eq!(starts(&ltc), 2);
+
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(100));
+        ltc.set_busy_policy(BusyPolicy::Reject);
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH2]);
+        ltc.start_conversion(LTC2983Channel::CH1).unwrap();
+        assert!(matches!(ltc.start_conversion(LTC2983Channel::CH2), Err(LTC2983Error::ConversionInProgress)));
+        assert!(matches!(ltc.start_multi_conversion(&[LTC2983Channel::CH2]), Err(LTC2983Error::ConversionInProgress)));
+        assert_eq!(starts(&ltc), 1);
+
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(3));
+        ltc.set_busy_policy(BusyPolicy::Queue);
+        ltc.start_conversion(LTC2983Channel::CH1).unwrap();
+        ltc.start_conversion(LTC2983Channel::CH2).unwrap();
+        assert_eq!(starts(&ltc), 2);
+        //the status was polled until the first conversion was done (3 busy polls and the done one) before the second started
+        let frames = &ltc.spi_device.frames;
+        let start_frames: Vec<usize> = (0..frames.len()).filter(|&i| frames[i] == [LTC2983_WRITE, 0x00, 0x00]).collect();
+        let polls_between = frames[start_frames[0]..start_frames[1]].iter().filter(|frame| **frame == [LTC2983_READ, 0x00, 0x00]).count();
+        assert_eq!(polls_between, 4);
+    }
+
+    #[test]
+    fn test_multi_channel_methods_take_arrays() {
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x00]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x68, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+
+        let channels = [LTC2983Channel::CH1, LTC2983Channel::CH3];
+        assert!(matches!(ltc.start_multi_conversion(&channels), Err(LTC2983Error::ChannelUnconfigured(LTC2983Channel::CH1))));
+        assert!(ltc.spi_device.writes.is_empty());
+        assume_diodes(&mut ltc, &channels);
+        ltc.start_multi_conversion(&channels).unwrap();
+        assert_eq!(ltc.spi_device.writes[0], vec![LTC2983_WRITE, 0x00, 0xf4, 0x00, 0x00, 0x00, 0x05]);
+        let results = ltc.read_multi_temperature(&channels);
+        assert_eq!(results.iter().map(|(channel, _)| *channel).collect::<Vec<_>>(), channels);
+        assert!(matches!(results[1], (LTC2983Channel::CH3, Ok(LTC2983Result::Valid(_)))));
+        assert_eq!(ltc.get_multi_temperature_avg(&channels, 2).unwrap(), vec![25., 26.]);
+    }
+
+    #[test]
+    fn test_read_all_packed() {
+        let mut chip = FakeChip::new();
+        chip.set_result(LTC2983Channel::CH1, [0x01, 0x00, 0x64, 0x66]);
+        chip.set_result(LTC2983Channel::CH2, [0x01, 0xfb, 0xbb, 0x67]);
+        chip.set_result(LTC2983Channel::CH3, [0x81, 0x00, 0x64, 0x66]);
+        chip.set_result(LTC2983Channel::CH4, [0x01, 0x06, 0x40, 0x00]);
+        let mut ltc = LTC2983::new(chip);
+        for channel in [LTC2983Channel::CH1, LTC2983Channel::CH2, LTC2983Channel::CH3, LTC2983Channel::CH4] {
+            ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), channel).unwrap();
+        }
+
+        let packed = ltc.read_all_packed().unwrap();
+        assert_eq!(packed[0], 2510);
+        assert_eq!(packed[1], -27315);
+        assert_eq!(packed[2], PACKED_FAULT); // sensor hard fault
+        assert_eq!(packed[3], PACKED_FAULT); // 400°C does not fit
+        assert!(packed[4..].iter().all(|&t| t == PACKED_FAULT));
+    }
+
+    #[test]
+    fn test_channel_config_builder() {
+        let diode = || ThermalProbeType::Diode(DiodeParameters::default());
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ChannelConfigBuilder::new()
+            .channel(LTC2983Channel::CH3, diode())
+            .channel(LTC2983Channel::CH1, diode())
+            .channel(LTC2983Channel::CH2, ThermalProbeType::SenseResistor(2000.))
+            .channel(LTC2983Channel::CH10, diode())
+            .commit(&mut ltc)
+            .unwrap();
+
+        let writes = &ltc.spi_device.writes;
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0][..3], [LTC2983_WRITE, 0x02, 0x00]);
+        assert_eq!(writes[0][3..7], diode().to_config_word().unwrap().to_be_bytes());
+        assert_eq!(writes[0][7..11], ThermalProbeType::SenseResistor(2000.).to_config_word().unwrap().to_be_bytes());
+        assert_eq!(writes[0][11..], diode().to_config_word().unwrap().to_be_bytes());
+        assert_eq!(writes[1][..3], [LTC2983_WRITE, 0x02, 0x24]);
+        assert!(matches!(ltc.channels[LTC2983Channel::CH2.index()], Some(ThermalProbeType::SenseResistor(_))));
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        let result = ChannelConfigBuilder::new()
+            .channel(LTC2983Channel::CH5, diode())
+            .channel(LTC2983Channel::CH5, diode())
+            .commit(&mut ltc);
+        assert!(matches!(result, Err(LTC2983Error::DuplicateChannel(LTC2983Channel::CH5))));
+        assert!(ltc.spi_device.writes.is_empty());
+    }
+
+    #[test]
+    fn test_rotation_read_back() {
+        let four_wire = RTDSensorConfiguration::default().wire_cnt(RTDWireCount::Wire4);
+        assert_eq!(four_wire.current_source_rotation(true).to_bits(), 0b1010);
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        let rotated = RTDSensorConfiguration::default().wire_cnt(RTDWireCount::Wire4).current_source_rotation(true);
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default().sensor_configuration(rotated)), LTC2983Channel::CH4).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH6).unwrap();
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default().ideality_factor(1.25)), LTC2983Channel::CH8).unwrap();
+
+        assert!(ltc.rotation_enabled(LTC2983Channel::CH4).unwrap());
+        assert!(!ltc.rotation_enabled(LTC2983Channel::CH6).unwrap());
+        assert!(!ltc.rotation_enabled(LTC2983Channel::CH8).unwrap());
+        match ltc.read_probe(LTC2983Channel::CH4).unwrap() {
+            Some(ThermalProbeType::RTD_PT100(param)) => assert_eq!(param.sensor_configuration.wire_cnt, RTDWireCount::Wire4),
+            probe => panic!("unexpected probe {probe:?}"),
+        }
+        match ltc.read_probe(LTC2983Channel::CH8).unwrap() {
+            Some(ThermalProbeType::Diode(param)) => assert_eq!(param.idealitiy_factor, Some(1.25)),
+            probe => panic!("unexpected probe {probe:?}"),
+        }
+        assert!(ltc.read_probe(LTC2983Channel::CH9).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_offline_board_configuration() {
+        let board = [
+            (LTC2983Channel::CH2, ThermalProbeType::SenseResistor(2000.)),
+            (LTC2983Channel::CH3, ThermalProbeType::RTD_PT100(RTDParameters::default().excitation_current(RTDExcitationCurrent::I100uA))),
+        ];
+        assert_eq!(offline::channel_config_writes(&board).unwrap(), vec![
+            (0x204, vec![0xe8, 0x1f, 0x40, 0x00, 0x60, 0x85, 0x40, 0x00]),
+        ]);
+        assert_eq!(offline::global_config_write(&GlobalConfiguration::default().rejection(RejectionFilter::Reject50Hz)), (0x0f0, vec![0x02]));
+        assert_eq!(offline::mux_delay_write(1_000), (0x0ff, vec![10]));
+    }
+
+    #[test]
+    fn test_cold_junction_validation() {
+        let thermocouple = || ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH2));
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(matches!(ltc.setup_channel(thermocouple(), LTC2983Channel::CH4), Err(LTC2983Error::InvalidConfig(ConfigError::ColdJunctionUnconfigured(LTC2983Channel::CH2)))));
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        assert!(matches!(ltc.setup_channel(thermocouple(), LTC2983Channel::CH4), Err(LTC2983Error::InvalidConfig(ConfigError::ColdJunctionUnconfigured(LTC2983Channel::CH2)))));
+        assert_eq!(ltc.spi_device.writes.len(), 1);
+
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(thermocouple(), LTC2983Channel::CH4).unwrap();
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ChannelConfigBuilder::new()
+            .channel(LTC2983Channel::CH4, thermocouple())
+            .channel(LTC2983Channel::CH2, ThermalProbeType::Diode(DiodeParameters::default()))
+            .commit(&mut ltc)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_custom_thermocouple_type() {
+        let standard = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default());
+        assert_eq!(standard.to_config_word().unwrap() >> 27, 2);
+        assert_eq!(standard.to_config_word().unwrap() & 0xfff, 0);
+
+        //offset (0x268 - 0x250) / 4 = 6 in bits 11-6, 10 - 1 entries in bits 5-0
+        let custom = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().custom_table(0x268, 10));
+        assert_eq!(custom.identifier(), 9);
+        assert_eq!(custom.kind(), SensorKind::Thermocouple_Custom);
+        assert_eq!(custom.to_config_word().unwrap() >> 27, 9);
+        assert_eq!(custom.to_config_word().unwrap() & 0xfff, (6 << 6) | 9);
+        match ThermalProbeType::from_config_word(custom.to_config_word().unwrap()) {
+            Some(ThermalProbeType::Thermocouple_Custom(param)) => assert_eq!(param.custom_table, Some(CustomTable::new(0x268, 10))),
+            other => panic!("{other:?}")
+        }
+
+        //a table with a single entry at the start of the custom sensor RAM has a pointer of 0
+        let first = ThermalProbeType::Thermocouple_Custom(ThermocoupleParameters::default().custom_table(0x250, 1));
+        assert_eq!(first.to_config_word().unwrap() & 0xfff, 0);
+        match ThermalProbeType::from_config_word(first.to_config_word().unwrap()) {
+            Some(ThermalProbeType::Thermocouple_Custom(param)) => assert_eq!(param.custom_table, Some(CustomTable::new(0x250, 1))),
+            other => panic!("{other:?}")
+        }
+
+        //the cache holds what was written
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(custom, LTC2983Channel::CH4).unwrap();
+        assert!(matches!(ltc.channels[LTC2983Channel::CH4.index()], Some(ThermalProbeType::Thermocouple_Custom(_))));
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH4), Some(SensorKind::Thermocouple_Custom));
+
+        for addr in [0x24F, 0x350, 0x3D0, 0x1250] {
+            let custom = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().custom_table(addr, 1));
+            assert_eq!(custom.to_config_word(), Err(ConfigError::CustomAddressOutOfRange(addr)));
+        }
+        let unaligned = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().custom_table(0x252, 1));
+        assert_eq!(unaligned.to_config_word(), Err(ConfigError::CustomAddressUnaligned(0x252)));
+
+        //0x34C + 22 * 6 = 0x3D0, the last entry ends at 0x3CF
+        let last = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().custom_table(0x34C, 22));
+        assert_eq!(last.to_config_word().unwrap() & 0xfff, (63 << 6) | 21);
+        for (address, entries) in [(0x34C, 23), (0x250, 0), (0x250, 65)] {
+            let custom = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().custom_table(address, entries));
+            assert_eq!(custom.to_config_word(), Err(ConfigError::CustomTableTooLong { address, entries }));
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_measure() {
+        let mut chip = FakeChip::new().busy_polls(3);
+        chip.set_result(LTC2983Channel::CH5, [0x01, 0x00, 0x64, 0x66]);
+        let mut delay = FakeDelay::default();
+        let mut ltc = LTC2983Async::with_poll_interval(chip, &mut delay, 1_000, 10_000);
+
+        block_on(ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH5)).unwrap();
+        assert!(matches!(block_on(ltc.measure(LTC2983Channel::CH5)), Ok(LTC2983Result::Valid(Reading::Temperature(_)))));
+        drop(ltc);
+        assert_eq!(delay.waited_ns, 3_000_000);
+
+        let mut delay = FakeDelay::default();
+        let mut ltc = LTC2983Async::with_poll_interval(FakeChip::new().stuck_channel(LTC2983Channel::CH5), &mut delay, 0, 50);
+        assert!(matches!(block_on(ltc.measure(LTC2983Channel::CH5)), Err(LTC2983Error::ConversionTimeout)));
+    }
+
+    #[test]
+    fn test_bus_with_chip_select() {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut ltc = LTC2983::with_bus(FakeBus(log.clone()), FakePin("cs", log.clone())).unwrap();
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        ltc.status().unwrap();
+
+        assert_eq!(*log.borrow(), [
+            "cs high",
+            "cs low", "write [2, 0, 0, 83]", "flush", "cs high",
+            "cs low", "transfer [3, 0, 0, 0]", "flush", "cs high",
+        ]);
+
+        //a delay operation fails the transaction, chip select is released anyway
+        log.borrow_mut().clear();
+        let res = ltc.spi_device.transaction(&mut [Operation::Write(&[0x02]), Operation::DelayNs(1_000), Operation::Write(&[0x00])]);
+        assert!(matches!(res, Err(bus::BusDeviceError::DelayUnsupported)));
+        assert_eq!(*log.borrow(), ["cs low", "write [2]", "flush", "cs high"]);
+    }
+
+    #[test]
+    fn test_thermistor_config_word() {
+        let thermistor = ThermalProbeType::Thermistor_44006_44031(ThermistorParameters::default().channel(LTC2983Channel::CH3));
+        // |31-27| 22, |26-22| CH3, |21-19| single ended, |18-15| auto range
+        assert_eq!(thermistor.to_config_word().unwrap(), (22 << 27) | (3 << 22) | (0b100 << 19) | (12 << 15));
+        match ThermalProbeType::from_config_word(thermistor.to_config_word().unwrap()) {
+            Some(ThermalProbeType::Thermistor_44006_44031(param)) => assert_eq!(param.r_sense_channel, LTC2983Channel::CH3),
+            probe => panic!("unexpected probe {probe:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sense_resistor_validation() {
+        let rtd = || ThermalProbeType::RTD_PT100(RTDParameters::default().channel(LTC2983Channel::CH3));
+        let thermistor = || ThermalProbeType::Thermistor_44006_44031(ThermistorParameters::default().channel(LTC2983Channel::CH3));
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(matches!(ltc.setup_channel(rtd(), LTC2983Channel::CH5), Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistorUnconfigured(LTC2983Channel::CH3)))));
+        assert!(matches!(ltc.setup_channel(thermistor(), LTC2983Channel::CH7), Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistorUnconfigured(LTC2983Channel::CH3)))));
+
+        ltc.setup_channel(ThermalProbeType::SenseResistor(10_000.), LTC2983Channel::CH3).unwrap();
+        ltc.setup_channel(rtd(), LTC2983Channel::CH5).unwrap();
+        ltc.setup_channel(thermistor(), LTC2983Channel::CH7).unwrap();
+        assert!(ltc.validate_channels().is_ok());
+
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        assert!(matches!(ltc.validate_channels(), Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistorUnconfigured(LTC2983Channel::CH3)))));
+    }
+
+    #[test]
+    fn test_raw_register_access() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.write_register(0x3cc, &[0xde, 0xad, 0xbe, 0xef]).unwrap();
+        assert_eq!(ltc.read_register(0x3cd, 3).unwrap(), vec![0xad, 0xbe, 0xef]);
+        assert_eq!(ltc.spi_device.writes, vec![vec![LTC2983_WRITE, 0x03, 0xcc, 0xde, 0xad, 0xbe, 0xef]]);
+
+        assert!(matches!(ltc.read_register(0x3cd, 4), Err(LTC2983Error::RegisterOutOfRange(0x3cd, 4))));
+        assert!(matches!(ltc.write_register(0x3d0, &[0x00]), Err(LTC2983Error::RegisterOutOfRange(0x3d0, 1))));
+        assert_eq!(ltc.spi_device.writes.len(), 1);
+    }
+
+    #[test]
+    fn test_fault_change_detection() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.spi_device.set_result(LTC2983Channel::CH6, [0x01, 0x00, 0x64, 0x66]);
+        assert!(!ltc.read_temperature_with_change(LTC2983Channel::CH6).unwrap().1);
+
+        ltc.spi_device.set_result(LTC2983Channel::CH6, [0x09, 0x00, 0x64, 0x66]);
+        let (result, changed) = ltc.read_temperature_with_change(LTC2983Channel::CH6).unwrap();
+        assert!(changed);
+        assert_eq!(result.faults(), FaultFlags::SENSOR_OVER_RANGE);
+        assert!(!ltc.read_temperature_with_change(LTC2983Channel::CH6).unwrap().1);
+
+        ltc.spi_device.set_result(LTC2983Channel::CH6, [0x01, 0x00, 0x64, 0x66]);
+        assert!(ltc.read_temperature_with_change(LTC2983Channel::CH6).unwrap().1);
+
+        //an accepted suspect result is valid, its faults still only change once
+        for policy in [SuspectPolicy::Accept, SuspectPolicy::AcceptIfOnly(FaultFlags::CJ_SOFT_FAULT)] {
+            let mut ltc = LTC2983::new(FakeChip::new());
+            ltc.set_suspect_policy(policy);
+            ltc.spi_device.set_result(LTC2983Channel::CH6, [0x11, 0x00, 0x64, 0x66]);
+            let changes: Vec<bool> = (0..3).map(|_| ltc.read_temperature_with_change(LTC2983Channel::CH6).unwrap().1).collect();
+            assert_eq!(changes, [true, false, false], "{policy:?}");
+            assert!(matches!(ltc.read_temperature(LTC2983Channel::CH6), Ok(LTC2983Result::Valid(_))));
+        }
+    }
+
+    #[test]
+    fn test_deserialize_channel_configuration() {
+        let probe: ThermalProbeType = serde_json::from_str(r#"{
+            "RTD_PT100": {
+                "r_sense_channel": "CH4",
+                "sensor_configuration": { "wire_cnt": "Wire4", "current_source_rotation": true },
+                "excitation_current": "I250uA"
+            }
+        }"#).unwrap();
+        let expected = ThermalProbeType::RTD_PT100(RTDParameters::default()
+            .channel(LTC2983Channel::CH4)
+            .sensor_configuration(RTDSensorConfiguration::default().wire_cnt(RTDWireCount::Wire4).current_source_rotation(true))
+            .excitation_current(RTDExcitationCurrent::I250uA));
+        assert_eq!(probe.to_config_word().unwrap(), expected.to_config_word().unwrap());
+
+        let json = serde_json::to_string(&ThermalProbeType::SenseResistor(2000.)).unwrap();
+        assert_eq!(json, r#"{"SenseResistor":2000.0}"#);
+        let config: GlobalConfiguration = serde_json::from_str(r#"{ "rejection": "Reject50Hz" }"#).unwrap();
+        assert_eq!(config, GlobalConfiguration::default().rejection(RejectionFilter::Reject50Hz));
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let map: ChannelMap = serde_json::from_str(r#"{
+            "CH4": { "RTD_PT100": { "r_sense_channel": "CH2" } },
+            "CH2": { "SenseResistor": 2000.0 }
+        }"#).unwrap();
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.apply_config(&map).unwrap();
+        assert!(matches!(ltc.read_probe(LTC2983Channel::CH2).unwrap(), Some(ThermalProbeType::SenseResistor(_))));
+        assert!(matches!(ltc.read_probe(LTC2983Channel::CH4).unwrap(), Some(ThermalProbeType::RTD_PT100(_))));
+
+        let map = ChannelMap::new()
+            .channel(LTC2983Channel::CH1, ThermalProbeType::Diode(DiodeParameters::default()))
+            .channel(LTC2983Channel::CH6, ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH5)));
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(matches!(ltc.apply_config(&map), Err(LTC2983Error::InvalidConfig(ConfigError::ColdJunctionUnconfigured(LTC2983Channel::CH5)))));
+        assert!(ltc.spi_device.writes.is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{:.1}", LTC2983Result::from([0x01, 0x00, 0x65, 0x33])), "25.3 °C");
+        assert_eq!(format!("{:.1}", LTC2983Result::from([0x11, 0x00, 0x5c, 0x66])), "23.1 °C (suspect: CJ soft fault)");
+        assert_eq!(LTC2983Result::from([0x80, 0x00, 0x00, 0x00]).to_string(), "INVALID (sensor hard fault)");
+        assert_eq!(format!("{:.2}", Reading::Voltage(Volts(0.5))), "0.50 V");
+
+        assert_eq!(FaultFlags::from_bits(0x4a).to_string(), "ADC hard fault, sensor over range, ADC out of range");
+        assert_eq!(FaultFlags::NONE.to_string(), "no faults");
+    }
+
+    #[test]
+    fn test_multi_conversion_progress() {
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(10)
+            .result_sequence(LTC2983Channel::CH2, &[[0x01, 0x00, 0x64, 0x00]])
+            .result_sequence(LTC2983Channel::CH5, &[[0x01, 0x00, 0x68, 0x00]]));
+        ltc.spi_device.set_result(LTC2983Channel::CH9, [0x01, 0x00, 0x68, 0x00]);
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH2, LTC2983Channel::CH5, LTC2983Channel::CH9]);
+        ltc.start_multi_conversion(&[LTC2983Channel::CH2, LTC2983Channel::CH5, LTC2983Channel::CH9]).unwrap();
+        assert_eq!(ltc.completed_channels().unwrap(), vec![LTC2983Channel::CH2, LTC2983Channel::CH5]);
+
+        ltc.spi_device.remaining_polls = 0;
+        assert_eq!(ltc.completed_channels().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_rtd_external_excitation() {
+        let word = |wire_cnt: RTDWireCount, external: bool| {
+            let config = RTDSensorConfiguration::default().wire_cnt(wire_cnt).external(external).current_source_rotation(true);
+            ThermalProbeType::RTD_PT100(RTDParameters::default().sensor_configuration(config).excitation_current(RTDExcitationCurrent::I100uA)).pack_config_word()
+        };
+        // |21-18| sensor configuration and |17-14| excitation current of the configuration word
+        let fields = |word: u32| ((word >> 18) & 0xf, (word >> 14) & 0xf);
+        assert_eq!(fields(word(RTDWireCount::Wire2, false)), (0b0001, 5));
+        assert_eq!(fields(word(RTDWireCount::Wire2, true)), (0b0000, 0));
+        assert_eq!(fields(word(RTDWireCount::Wire3, false)), (0b0101, 5));
+        assert_eq!(fields(word(RTDWireCount::Wire3, true)), (0b0100, 0));
+        assert_eq!(fields(word(RTDWireCount::Wire4, false)), (0b1010, 5));
+        assert_eq!(fields(word(RTDWireCount::Wire4, true)), (0b1000, 0));
+
+        let decoded = RTDSensorConfiguration::from_bits(0b1000);
+        assert!(decoded.external && !decoded.current_source_rotation);
+    }
+
+    #[test]
+    fn test_rtd_sensor_configuration_bits() {
+        // wire count, current source rotation, external excitation => |21-18| of the configuration word
+        let table = [
+            (RTDWireCount::Wire2,             false, false, 0b0001),
+            (RTDWireCount::Wire2,             false, true,  0b0000),
+            (RTDWireCount::Wire2,             true,  false, 0b0001),
+            (RTDWireCount::Wire2,             true,  true,  0b0000),
+            (RTDWireCount::Wire3,             false, false, 0b0101),
+            (RTDWireCount::Wire3,             false, true,  0b0100),
+            (RTDWireCount::Wire3,             true,  false, 0b0101),
+            (RTDWireCount::Wire3,             true,  true,  0b0100),
+            (RTDWireCount::Wire4,             false, false, 0b1001),
+            (RTDWireCount::Wire4,             false, true,  0b1000),
+            (RTDWireCount::Wire4,             true,  false, 0b1010),
+            (RTDWireCount::Wire4,             true,  true,  0b1000),
+            (RTDWireCount::Wire4KelvinRsense, false, false, 0b1101),
+            (RTDWireCount::Wire4KelvinRsense, false, true,  0b1100),
+            (RTDWireCount::Wire4KelvinRsense, true,  false, 0b1110),
+            (RTDWireCount::Wire4KelvinRsense, true,  true,  0b1100),
+        ];
+        for (wire_cnt, rotation, external, bits) in table {
+            let config = RTDSensorConfiguration::default().wire_cnt(wire_cnt.clone()).current_source_rotation(rotation).external(external);
+            assert_eq!(config.to_bits(), bits, "{wire_cnt:?} rotation: {rotation} external: {external}");
+            assert_eq!(RTDSensorConfiguration::from_bits(bits).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn test_wiring_fault() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH4).unwrap();
+        ltc.setup_channel(ThermalProbeType::Thermistor_44006_44031(ThermistorParameters::default()), LTC2983Channel::CH6).unwrap();
+
+        ltc.spi_device.set_result(LTC2983Channel::CH4, [0x88, 0x00, 0x00, 0x00]);
+        assert_eq!(ltc.read_wiring_fault(LTC2983Channel::CH4).unwrap(), Some(WiringFault::Open));
+        ltc.spi_device.set_result(LTC2983Channel::CH4, [0x05, 0xfc, 0xe0, 0x00]);
+        assert_eq!(ltc.read_wiring_fault(LTC2983Channel::CH4).unwrap(), Some(WiringFault::Shorted));
+        ltc.spi_device.set_result(LTC2983Channel::CH4, [0x01, 0x00, 0x64, 0x66]);
+        assert_eq!(ltc.read_wiring_fault(LTC2983Channel::CH4).unwrap(), None);
+
+        //a NTC thermistor reads too hot when shorted
+        ltc.spi_device.set_result(LTC2983Channel::CH6, [0x09, 0x04, 0x00, 0x00]);
+        assert_eq!(ltc.read_wiring_fault(LTC2983Channel::CH6).unwrap(), Some(WiringFault::Shorted));
+        ltc.spi_device.set_result(LTC2983Channel::CH6, [0x85, 0x00, 0x00, 0x00]);
+        assert_eq!(ltc.read_wiring_fault(LTC2983Channel::CH6).unwrap(), Some(WiringFault::Open));
+
+        //thermocouples have their own open circuit detection
+        let thermocouple = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default());
+        assert_eq!(thermocouple.wiring_fault(FaultFlags::SENSOR_HARD_FAULT), None);
+        assert_eq!(WiringFault::Open.to_string(), "sensor open");
+    }
+
+    #[test]
+    fn test_read_with_cold_junction() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        ltc.setup_channel(ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH3)), LTC2983Channel::CH1).unwrap();
+        ltc.setup_channel(ThermalProbeType::Thermocouple_T(ThermocoupleParameters::default()), LTC2983Channel::CH5).unwrap();
+
+        ltc.spi_device.set_result(LTC2983Channel::CH1, [0x01, 0x06, 0x40, 0x00]);
+        ltc.spi_device.set_result(LTC2983Channel::CH3, [0x01, 0x00, 0x64, 0x66]);
+        let reading = ltc.read_with_cold_junction(LTC2983Channel::CH1).unwrap();
+        assert!(matches!(reading.thermocouple, LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if t == 400.));
+        assert!(matches!(reading.cold_junction, LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if (t - 25.1).abs() < 0.01));
+
+        assert!(matches!(ltc.read_with_cold_junction(LTC2983Channel::CH5), Err(LTC2983Error::NoColdJunction(LTC2983Channel::CH5))));
+        assert!(matches!(ltc.read_with_cold_junction(LTC2983Channel::CH3), Err(LTC2983Error::NoColdJunction(LTC2983Channel::CH3))));
+        assert!(matches!(ltc.read_with_cold_junction(LTC2983Channel::CH7), Err(LTC2983Error::ChannelUnconfigured(LTC2983Channel::CH7))));
+    }
+
+    #[test]
+    fn test_conversion_time() {
+        let rejection = RejectionFilter::Reject50And60Hz;
+        let thermocouple = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default());
+        assert_eq!(thermocouple.conversion_time(&rejection), Duration::from_millis(168));
+        assert_eq!(thermocouple.conversion_time(&RejectionFilter::Reject60Hz), Duration::from_millis(140));
+
+        let rotated = RTDParameters::default().sensor_configuration(RTDSensorConfiguration::default().wire_cnt(RTDWireCount::Wire4).current_source_rotation(true));
+        assert_eq!(ThermalProbeType::RTD_PT100(rotated).conversion_time(&rejection), Duration::from_millis(252));
+        assert_eq!(ThermalProbeType::RTD_PT100(RTDParameters::default()).conversion_time(&rejection), Duration::from_millis(168));
+        let thermistor = ThermistorParameters::default();
+        assert_eq!(ThermalProbeType::Thermistor_YSI400(thermistor.clone()).conversion_time(&rejection), Duration::from_millis(252));
+        let fixed_current = thermistor.excitation_current(ThermistorExcitationCurrent::I10uA);
+        assert_eq!(ThermalProbeType::Thermistor_YSI400(fixed_current).conversion_time(&rejection), Duration::from_millis(168));
+        let diode = DiodeParameters::default().num_reading(DiodeReadingCount::READ3);
+        assert_eq!(ThermalProbeType::Diode(diode).conversion_time(&rejection), Duration::from_millis(252));
+        assert_eq!(ThermalProbeType::SenseResistor(2000.).conversion_time(&rejection), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_eeprom() {
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(2));
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.save_to_eeprom().unwrap();
+        assert_eq!(ltc.spi_device.writes[1], [LTC2983_WRITE, 0x00, 0xB0, 0xA5, 0x3C, 0x0F, 0x5A]);
+        assert_eq!(ltc.spi_device.writes[2], [LTC2983_WRITE, 0x00, 0x00, 0x95]);
+
+        //a freshly powered up driver learns the sensors from the restored registers
+        let mut chip = FakeChip::new();
+        chip.mem[0x204..0x208].copy_from_slice(&ThermalProbeType::SenseResistor(2000.).to_config_word().unwrap().to_be_bytes());
+        let rtd = ThermalProbeType::RTD_PT100(RTDParameters::default());
+        chip.mem[0x20C..0x210].copy_from_slice(&rtd.to_config_word().unwrap().to_be_bytes());
+        chip.mem[GLOBAL_CONFIG_REGISTER as usize] = 0x06;
+        let mut ltc = LTC2983::new(chip);
+        ltc.restore_from_eeprom().unwrap();
+        assert_eq!(ltc.spi_device.writes[1], [LTC2983_WRITE, 0x00, 0x00, 0x96]);
+        assert_eq!(ltc.channels[LTC2983Channel::CH4.index()].as_ref().map(|probe| probe.to_config_word().unwrap()), Some(rtd.to_config_word().unwrap()));
+        assert!(ltc.channels[LTC2983Channel::CH1.index()].is_none());
+        assert_eq!(ltc.global_configuration(), GlobalConfiguration::default().fahrenheit(true).rejection(RejectionFilter::Reject50Hz));
+
+        ltc.spi_device.mem[EEPROM_STATUS_REGISTER as usize] = 0x02;
+        assert!(matches!(ltc.save_to_eeprom(), Err(LTC2983Error::EepromFailed(0x02))));
+    }
+
+    #[test]
+    fn test_enabled_channels() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(ltc.enabled_channels().unwrap().is_empty());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH4).unwrap();
+        ltc.setup_channel(ThermalProbeType::DirectADC(DirectADCParameters::default()), LTC2983Channel::CH20).unwrap();
+        assert_eq!(ltc.enabled_channels().unwrap(), [LTC2983Channel::CH2, LTC2983Channel::CH4, LTC2983Channel::CH20]);
+        assert!(ltc.channel_enabled(LTC2983Channel::CH20));
+    }
+
+    #[test]
+    fn test_start_all_configured() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(matches!(ltc.start_all_configured(), Err(LTC2983Error::NoChannelsConfigured)));
+        assert_eq!(ltc.conversion_state(), ConversionState::Idle);
+
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH4).unwrap();
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH7).unwrap();
+        assert_eq!(ltc.start_all_configured().unwrap(), [LTC2983Channel::CH4, LTC2983Channel::CH7]);
+        assert_eq!(ltc.spi_device.mem[0x0F4..0x0F8], (LTC2983Channel::CH4.mask() | LTC2983Channel::CH7.mask()).to_be_bytes());
+    }
+
+    #[test]
+    fn test_configured_channel_handle() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        let diode = ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        assert_eq!(diode.channel(), LTC2983Channel::CH3);
+
+        ltc.spi_device.set_result(LTC2983Channel::CH3, [0x01, 0x00, 0x64, 0x66]);
+        assert!(matches!(ltc.read_configured(&diode).unwrap(), LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if (t - 25.1).abs() < 0.01));
+    }
+
+    #[test]
+    fn test_rtd_kelvin_rsense() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        let kelvin = RTDSensorConfiguration::default().wire_cnt(RTDWireCount::Wire4KelvinRsense);
+        let rtd = |config: RTDSensorConfiguration| ThermalProbeType::RTD_PT100(RTDParameters::default()
+            .channel(LTC2983Channel::CH2)
+            .sensor_configuration(config)
+            .excitation_current(RTDExcitationCurrent::I100uA));
+
+        //PT-100 (0b01100), Rsense CH2, Kelvin Rsense (0b11) with rotation (0b10), 100µA (0b0101), European curve
+        ltc.setup_channel(rtd(kelvin.clone().current_source_rotation(true)), LTC2983Channel::CH4).unwrap();
+        assert_eq!(ltc.spi_device.mem[0x20C..0x210], [0x60, 0xB9, 0x40, 0x00]);
+        ltc.setup_channel(rtd(kelvin.clone()), LTC2983Channel::CH4).unwrap();
+        assert_eq!(ltc.spi_device.mem[0x20C..0x210], [0x60, 0xB5, 0x40, 0x00]);
+
+        let wire3 = RTDSensorConfiguration::default().wire_cnt(RTDWireCount::Wire3).current_source_rotation(true);
+        assert!(matches!(ltc.setup_channel(rtd(wire3), LTC2983Channel::CH6), Err(LTC2983Error::InvalidConfig(ConfigError::UnsupportedRotation))));
+        let external = kelvin.external(true).current_source_rotation(true);
+        assert!(matches!(ltc.setup_channel(rtd(external), LTC2983Channel::CH6), Err(LTC2983Error::InvalidConfig(ConfigError::UnsupportedRotation))));
+    }
+
+    #[test]
+    fn test_conversion_check() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        assert!(ltc.read_temperature(LTC2983Channel::CH3).is_ok());
+
+        ltc.set_conversion_check(true);
+        assert!(matches!(ltc.read_temperature(LTC2983Channel::CH3), Err(LTC2983Error::NoConversionYet(LTC2983Channel::CH3))));
+        ltc.measure(LTC2983Channel::CH3).unwrap();
+        assert!(ltc.read_temperature(LTC2983Channel::CH3).is_ok());
+
+        //converted by someone else, the status register still shows the channel
+        let mut chip = FakeChip::new();
+        chip.mem[STATUS_REGISTER as usize] = 0x40 | LTC2983Channel::CH5.identifier() as u8;
+        let mut ltc = LTC2983::new(chip);
+        ltc.set_conversion_check(true);
+        assert!(ltc.read_temperature(LTC2983Channel::CH5).is_ok());
+        assert!(matches!(ltc.read_temperature(LTC2983Channel::CH6), Err(LTC2983Error::NoConversionYet(LTC2983Channel::CH6))));
+    }
+
+    #[test]
+    fn test_control_pins() {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut delay = FakeDelay { waited_ns: 0 };
+        let pins = ControlPins::new(FakePin("reset", log.clone()), FakeInterrupt(3), &mut delay);
+        let mut ltc = LTC2983::new(FakeChip::new()).with_pins(pins);
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        assert!(matches!(ltc.wait_for_interrupt(200), Err(LTC2983Error::ConversionTimeout)));
+        ltc.wait_for_interrupt(200).unwrap();
+        assert_eq!(ltc.conversion_state(), ConversionState::Done);
+
+        ltc.hard_reset().unwrap();
+        assert_eq!(*log.borrow(), ["reset low", "reset high"]);
+        assert!(ltc.channels.iter().all(Option::is_none));
+        assert_eq!(ltc.conversion_state(), ConversionState::Idle);
+        drop(ltc);
+        assert_eq!(delay.waited_ns, 2 * 100_000 + 1_000_000);
+    }
+
+    #[test]
+    fn test_threshold_monitor() {
+        let mut monitor = ThresholdMonitor::new(LTC2983Channel::CH3, 20., 30.).hysteresis(1.);
+        assert_eq!(monitor.classify(25.), ThresholdLevel::Within);
+        assert_eq!(monitor.classify(30.5), ThresholdLevel::Above);
+        assert_eq!(monitor.classify(29.5), ThresholdLevel::Above);
+        assert_eq!(monitor.classify(28.9), ThresholdLevel::Within);
+        assert_eq!(monitor.classify(19.), ThresholdLevel::Below);
+        assert_eq!(monitor.classify(20.5), ThresholdLevel::Below);
+        assert_eq!(monitor.classify(21.5), ThresholdLevel::Within);
+
+        let chip = FakeChip::new().result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x66], [0x81, 0x00, 0x00, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        let mut monitor = ThresholdMonitor::new(LTC2983Channel::CH3, 0., 25.);
+        assert!(matches!(monitor.measure(&mut ltc).unwrap(), (Some(ThresholdLevel::Above), LTC2983Result::Valid(_))));
+        assert!(matches!(monitor.measure(&mut ltc).unwrap(), (None, LTC2983Result::Invalid(_))));
+        assert_eq!(monitor.level(), ThresholdLevel::Above);
+    }
+
+    #[test]
+    fn test_rtd_curve_validation() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        let its_90 = RTDParameters::default().curve(RTDCurve::ITS_90);
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(its_90.clone()), LTC2983Channel::CH4).unwrap();
+        assert_eq!(ltc.spi_device.mem[0x20E] & 0x30, 0x30);
+
+        assert!(matches!(ltc.setup_channel(ThermalProbeType::RTD_NI120(its_90), LTC2983Channel::CH6), Err(LTC2983Error::InvalidConfig(ConfigError::InvalidRTDCurve))));
+        ltc.setup_channel(ThermalProbeType::RTD_NI120(RTDParameters::default()), LTC2983Channel::CH6).unwrap();
+    }
+
+    #[test]
+    fn test_poll_and_read() {
+        let chip = FakeChip::new().busy_polls(1).result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x66]]);
+        let mut ltc = LTC2983::new(chip);
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        assert!(ltc.poll_and_read(LTC2983Channel::CH3).unwrap().is_none());
+        let result = ltc.poll_and_read(LTC2983Channel::CH3).unwrap().unwrap();
+        assert!(matches!(result, LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if (t - 25.1).abs() < 0.01));
+        assert_eq!(ltc.conversion_state(), ConversionState::Idle);
+    }
+
+    #[test]
+    fn test_golden_config_words() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        let diode = ThermalProbeType::Diode(DiodeParameters::default());
+        let thermocouple = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH3));
+        ltc.setup_channel(diode.clone(), LTC2983Channel::CH3).unwrap();
+        ltc.setup_channel(thermocouple.clone(), LTC2983Channel::CH1).unwrap();
+
+        assert_eq!(ltc.read_channel_config_raw(LTC2983Channel::CH3).unwrap(), diode.to_config_word().unwrap());
+        //type K (2), cold junction CH3, single-ended, 10µA open circuit current
+        assert_eq!(thermocouple.to_config_word().unwrap(), 0x10F0_0000);
+        assert_eq!(ltc.read_channel_config_raw(LTC2983Channel::CH1).unwrap(), 0x10F0_0000);
+        assert_eq!(ltc.read_channel_config_raw(LTC2983Channel::CH2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_config_word_errors() {
+        assert_eq!(ThermalProbeType::SenseResistor(2000.).to_config_word(), Ok(0xe81f_4000));
+        assert_eq!(ThermalProbeType::SenseResistor(-1.).to_config_word(), Err(ConfigError::SenseResistanceOutOfRange(-1.)));
+        assert_eq!(ThermalProbeType::SenseResistor(200_000.).to_config_word(), Err(ConfigError::SenseResistanceOutOfRange(200_000.)));
+        let diode = DiodeParameters::default().ideality_factor(4.5);
+        assert_eq!(ThermalProbeType::Diode(diode).to_config_word(), Err(ConfigError::IdealityFactorOutOfRange(4.5)));
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        let result = ltc.setup_channel(ThermalProbeType::SenseResistor(f32::NAN), LTC2983Channel::CH2);
+        assert!(matches!(result, Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistanceOutOfRange(_)))));
+        assert!(ltc.spi_device.writes.is_empty());
+    }
+
+    #[test]
+    fn test_setup_while_busy() {
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(3));
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        let writes = ltc.spi_device.writes.len();
+        assert!(matches!(ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH5), Err(LTC2983Error::DeviceBusy)));
+        assert_eq!(ltc.spi_device.writes.len(), writes);
+
+        ltc.set_busy_policy(BusyPolicy::Queue);
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH5).unwrap();
+        assert!(ltc.status().unwrap().done());
+    }
+
+    #[test]
+    fn test_configured_kind() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH4).unwrap();
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH2), Some(SensorKind::SenseResistor));
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH4), Some(SensorKind::RTD_PT100));
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH5), None);
+
+        ltc.reset().unwrap();
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH4), None);
+        assert!(ltc.enabled_channels().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let mut ltc = LTC2983::new(FakeChip::new().failing_transactions(2));
+        assert!(matches!(ltc.status(), Err(LTC2983Error::SpiError(FakeSpiError))));
+
+        let attempts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = attempts.clone();
+        ltc.set_retry_policy(2, move |attempt| log.lock().unwrap().push(attempt));
+        ltc.spi_device.failures = 2;
+        assert!(ltc.status().unwrap().done());
+        assert_eq!(*attempts.lock().unwrap(), [1, 2]);
+
+        ltc.spi_device.failures = 3;
+        assert!(matches!(ltc.start_conversion(LTC2983Channel::CH1), Err(LTC2983Error::SpiError(FakeSpiError))));
+        assert!(ltc.spi_device.writes.is_empty());
+    }
+
+    #[test]
+    fn test_read_multi_channel_mask() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(ltc.read_multi_channel_mask().unwrap().is_empty());
+        let channels = [LTC2983Channel::CH1, LTC2983Channel::CH9, LTC2983Channel::CH20];
+        assume_diodes(&mut ltc, &channels);
+        ltc.start_multi_conversion(&channels).unwrap();
+        assert_eq!(ltc.read_multi_channel_mask().unwrap(), channels);
+    }
+
+    #[test]
+    fn test_big_endian_framing() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH1, LTC2983Channel::CH20]);
+        ltc.start_conversion(LTC2983Channel::CH20).unwrap();
+        assert_eq!(ltc.spi_device.writes, [vec![LTC2983_WRITE, 0x00, 0x00, 0x94]]);
+
+        ltc.spi_device.writes.clear();
+        ltc.start_multi_conversion(&[LTC2983Channel::CH1, LTC2983Channel::CH20]).unwrap();
+        assert_eq!(ltc.spi_device.writes, [
+            vec![LTC2983_WRITE, 0x00, 0xF4, 0x00, 0x08, 0x00, 0x01],
+            vec![LTC2983_WRITE, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00],
+            vec![LTC2983_WRITE, 0x00, 0x5C, 0x00, 0x00, 0x00, 0x00],
+            vec![LTC2983_WRITE, 0x00, 0x00, 0x80],
+        ]);
+
+        ltc.spi_device.writes.clear();
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH20).unwrap();
+        assert_eq!(ltc.spi_device.writes, [vec![LTC2983_WRITE, 0x02, 0x4C, 0xE8, 0x1F, 0x40, 0x00]]);
+    }
+
+    #[test]
+    fn test_multi_temperature_avg_per_channel_rounds() {
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x00], [0x01, 0x00, 0x68, 0x00], [0x01, 0x00, 0x6C, 0x00]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x68, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH1, LTC2983Channel::CH3]);
+
+        let averages = ltc.get_multi_temperature_avg_rounds(&[(LTC2983Channel::CH1, 3), (LTC2983Channel::CH3, 1)]).unwrap();
+        assert_eq!(averages, vec![(LTC2983Channel::CH1, 26.), (LTC2983Channel::CH3, 26.)]);
+
+        //the first sweep converts both channels, the other two only CH1
+        let masks: Vec<_> = ltc.spi_device.writes.iter().filter(|w| w[1..3] == [0x00, 0xf4]).map(|w| w[3..].to_vec()).collect();
+        assert_eq!(masks, vec![vec![0, 0, 0, 0x05], vec![0, 0, 0, 0x01], vec![0, 0, 0, 0x01]]);
+    }
+
+    #[test]
+    fn test_multi_temperature_avg_rounds_gives_up_on_invalid_channel() {
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x00]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x80, 0x00, 0x00, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH1, LTC2983Channel::CH3]);
+
+        let res = ltc.get_multi_temperature_avg_rounds(&[(LTC2983Channel::CH1, 2), (LTC2983Channel::CH3, 2)]);
+        assert!(matches!(res, Err(LTC2983Error::SamplesRejected(3, 2))));
+        //two sweeps of both channels, then CH3 alone until its two retries are used up
+        let sweeps = ltc.spi_device.writes.iter().filter(|w| w[1..3] == [0x00, 0xf4]).count();
+        assert_eq!(sweeps, 3);
+
+        let mut ltc = LTC2983::new(FakeChip::new().failing_transactions(100));
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH1]);
+        assert!(matches!(ltc.get_multi_temperature_avg_rounds(&[(LTC2983Channel::CH1, 1)]), Err(LTC2983Error::SpiError(_))));
+    }
+
+    #[test]
+    fn test_reinitialize() {
+        let map = ChannelMap::new()
+            .channel(LTC2983Channel::CH2, ThermalProbeType::SenseResistor(2000.))
+            .channel(LTC2983Channel::CH4, ThermalProbeType::RTD_PT100(RTDParameters::default()));
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH7).unwrap();
+
+        ltc.reinitialize(&map).unwrap();
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH7), None);
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH4), Some(SensorKind::RTD_PT100));
+        assert_eq!(ltc.read_channel_config_raw(LTC2983Channel::CH7).unwrap(), 0);
+        assert_eq!(ltc.read_channel_config_raw(LTC2983Channel::CH2).unwrap(), 0xE81F4000);
+    }
+
+    #[test]
+    fn test_channel_stats() {
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH1, &[
+            [0x01, 0x00, 0x64, 0x00], // 25.0
+            [0x81, 0x00, 0x00, 0x00],
+            [0x01, 0x00, 0x6C, 0x00], // 27.0
+            [0x09, 0x00, 0x50, 0x00], // suspect 20.0
+            [0x01, 0x00, 0x68, 0x00], // 26.0
+        ]));
+        assert_eq!(ltc.channel_stats(LTC2983Channel::CH1), None);
+        for _ in 0..5 {
+            ltc.measure(LTC2983Channel::CH1).unwrap();
+        }
+        assert_eq!(ltc.channel_stats(LTC2983Channel::CH1), Some(ChannelStats { min: 25., max: 27., count: 3 }));
+
+        ltc.reset_stats(LTC2983Channel::CH1);
+        assert_eq!(ltc.channel_stats(LTC2983Channel::CH1), None);
+    }
+
+    #[test]
+    fn test_single_ended_thermocouple_on_ch1() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH2).unwrap();
+        let probe = ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH2));
+        ltc.setup_channel(probe, LTC2983Channel::CH1).unwrap();
+        //bit 21 selects the single-ended measurement against COM
+        assert_eq!(ltc.read_channel_config_raw(LTC2983Channel::CH1).unwrap() & (1 << 21), 1 << 21);
+    }
+
+    #[test]
+    fn test_read_all() {
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH4, &[[0x01, 0x00, 0x64, 0x00]])
+            .result_sequence(LTC2983Channel::CH9, &[[0x81, 0x00, 0x00, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+        assert!(ltc.read_all().unwrap().is_empty());
+        assert!(ltc.spi_device.writes.is_empty());
+
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH4).unwrap();
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH9).unwrap();
+
+        let results = ltc.read_all().unwrap();
+        assert_eq!(results.keys().copied().collect::<Vec<_>>(), vec![LTC2983Channel::CH4, LTC2983Channel::CH9]);
+        assert!(matches!(results[&LTC2983Channel::CH4], LTC2983Result::Valid(_)));
+        assert!(matches!(results[&LTC2983Channel::CH9], LTC2983Result::Invalid(_)));
+    }
+
+    #[test]
+    fn test_range_faults() {
+        let over_temperature = FaultFlags::from_bits(0x09);
+        assert!(over_temperature.sensor_over_range());
+        assert!(!over_temperature.sensor_under_range());
+        assert!(!over_temperature.adc_out_of_range());
+
+        let adc = FaultFlags::from_bits(0x03);
+        assert!(adc.adc_out_of_range());
+        assert!(!adc.sensor_over_range());
+        assert!(FaultFlags::from_bits(0x05).sensor_under_range());
+    }
+
+    #[test]
+    fn test_measure_timed() {
+        struct SlowWaiter(FakeClock);
+        impl ConversionWaiter for SlowWaiter {
+            fn wait<E>(&mut self, done: &mut dyn FnMut() -> Result<bool, E>) -> Result<bool, E> {
+                self.0.advance(Duration::from_millis(5));
+                done()
+            }
+        }
+        let clock = FakeClock::default();
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x00]]), SlowWaiter(clock.clone()));
+        ltc.set_clock(clock);
+        let (result, elapsed) = ltc.measure_timed(LTC2983Channel::CH3).unwrap();
+        assert!(matches!(result, LTC2983Result::Valid(_)));
+        assert_eq!(elapsed, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_measure_with_rejection_restores_the_filter() {
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x00]]));
+        ltc.set_global_configuration(GlobalConfiguration::default().fahrenheit(true).rejection(RejectionFilter::Reject50And60Hz)).unwrap();
+
+        ltc.measure_with_rejection(LTC2983Channel::CH3, RejectionFilter::Reject50Hz).unwrap();
+        let global_writes: Vec<_> = ltc.spi_device.writes.iter().filter(|w| w[1..3] == [0x00, 0xf0]).map(|w| w[3]).collect();
+        assert_eq!(global_writes, vec![0x04, 0x06, 0x04]);
+        assert_eq!(ltc.global_configuration().temperature_unit(), TemperatureUnit::Fahrenheit);
+        assert_eq!(ltc.global_configuration().rejection_filter(), RejectionFilter::Reject50And60Hz);
+    }
+
+    #[test]
+    fn test_diode_offset() {
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH2, &[
+            [0x01, 0x00, 0x64, 0x00], // 25.0
+            [0x09, 0x00, 0x68, 0x00], // suspect 26.0
+        ]));
+        let probe = ThermalProbeType::Diode(DiodeParameters::default().offset_c(-1.5));
+        let plain_word = ThermalProbeType::Diode(DiodeParameters::default()).to_config_word().unwrap();
+        assert_eq!(probe.to_config_word().unwrap(), plain_word);
+        ltc.setup_channel(probe, LTC2983Channel::CH2).unwrap();
+
+        assert!(matches!(ltc.measure(LTC2983Channel::CH2).unwrap().result, LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if t == 23.5));
+        assert!(matches!(ltc.measure(LTC2983Channel::CH2).unwrap().result, LTC2983Result::Suspect(Reading::Temperature(Celsius(t)), _) if t == 24.5));
+    }
+
+    #[test]
+    fn test_linearization() {
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH3, &[
+            [0x01, 0x00, 0x64, 0x00], // 25.0
+            [0x09, 0x00, 0x64, 0x00], // suspect 25.0
+        ]));
+        ltc.set_linearization(LTC2983Channel::CH3, |t| t + 1.);
+        assert!(matches!(ltc.measure(LTC2983Channel::CH3).unwrap().result, LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if t == 26.));
+        //suspects are left uncorrected
+        assert!(matches!(ltc.measure(LTC2983Channel::CH3).unwrap().result, LTC2983Result::Suspect(Reading::Temperature(Celsius(t)), _) if t == 25.));
+
+        ltc.clear_linearization(LTC2983Channel::CH3);
+        assert!(matches!(ltc.measure(LTC2983Channel::CH3).unwrap().result, LTC2983Result::Valid(Reading::Temperature(Celsius(t))) if t == 25.));
+    }
+
+    #[test]
+    fn test_diode_read3_80ua_averaged() {
+        let param = DiodeParameters::default()
+            .num_reading(DiodeReadingCount::READ3)
+            .excitation_current(DiodeExcitationCurrent::I80uA)
+            .use_avg(true);
+        // |26| single-ended, |25| 3 readings, |24| averaging on, |23-22| 80µA
+        assert_eq!(param.to_bits(), 0x07C0_0000);
+        assert_eq!(ThermalProbeType::Diode(param).to_config_word().unwrap(), 0xE7C0_0000);
+    }
+
+    #[test]
+    fn test_all_channels() {
+        assert_eq!(LTC2983Channel::all().count(), 20);
+        assert_eq!(LTC2983Channel::all().fold(0, |mask, channel| mask | channel.mask()), 0x000F_FFFF);
+        assert!(LTC2983Channel::all().zip(1..).all(|(channel, identifier)| channel.identifier() == identifier));
+    }
+
+    #[test]
+    fn test_channel_from_identifier() {
+        for channel in LTC2983Channel::all() {
+            assert_eq!(LTC2983Channel::from_identifier(channel.identifier()), Some(channel));
+            assert_eq!(LTC2983Channel::try_from(channel.identifier() as u8), Ok(channel));
+        }
+        assert_eq!(LTC2983Channel::try_from(0), Err(InvalidChannel(0)));
+        assert_eq!(LTC2983Channel::try_from(21), Err(InvalidChannel(21)));
+        assert_eq!(LTC2983Channel::from_identifier(21), None);
+    }
+
+    #[test]
+    fn test_wait_until_ready() {
+        let mut chip = FakeChip::new();
+        chip.mem[STATUS_REGISTER as usize] = 0x80; // still starting up
+        chip.remaining_polls = 3;
+        let mut ltc = LTC2983::new(chip);
+        let mut delay = FakeDelay::default();
+        ltc.wait_until_ready(&mut &mut delay, 100).unwrap();
+        assert_eq!(delay.waited_ns, 3_000_000);
+
+        let mut chip = FakeChip::new();
+        chip.mem[STATUS_REGISTER as usize] = 0x00; // no response
+        let mut ltc = LTC2983::new(chip);
+        assert!(matches!(ltc.wait_until_ready(&mut &mut delay, 10), Err(LTC2983Error::DeviceNotReady)));
+    }
+
+    #[test]
+    fn test_thermistor_excitation_modes() {
+        let thermistor = |config, mode, current| ThermalProbeType::Thermistor_44006_44031(
+            ThermistorParameters::default().sensor_configuration(config).excitation_mode(mode).excitation_current(current));
+        let matrix = [
+            (SensorConfiguration::SingleEnded, ThermistorExcitationMode::NoSharing, ThermistorExcitationCurrent::AutoRange, Ok(0b100)),
+            (SensorConfiguration::SingleEnded, ThermistorExcitationMode::Sharing, ThermistorExcitationCurrent::I10uA, Ok(0b100)),
+            (SensorConfiguration::SingleEnded, ThermistorExcitationMode::SharingRotation, ThermistorExcitationCurrent::I10uA, Err(ConfigError::UnsupportedThermistorRotation)),
+            (SensorConfiguration::SingleEnded, ThermistorExcitationMode::SharingRotation, ThermistorExcitationCurrent::AutoRange, Err(ConfigError::UnsupportedThermistorRotation)),
+            (SensorConfiguration::Differential, ThermistorExcitationMode::NoSharing, ThermistorExcitationCurrent::AutoRange, Ok(0b000)),
+            (SensorConfiguration::Differential, ThermistorExcitationMode::NoSharing, ThermistorExcitationCurrent::I1mA, Ok(0b000)),
+            (SensorConfiguration::Differential, ThermistorExcitationMode::Sharing, ThermistorExcitationCurrent::AutoRange, Ok(0b010)),
+            (SensorConfiguration::Differential, ThermistorExcitationMode::Sharing, ThermistorExcitationCurrent::I250nA, Ok(0b010)),
+            (SensorConfiguration::Differential, ThermistorExcitationMode::SharingRotation, ThermistorExcitationCurrent::I10uA, Ok(0b001)),
+            (SensorConfiguration::Differential, ThermistorExcitationMode::SharingRotation, ThermistorExcitationCurrent::AutoRange, Err(ConfigError::AutoRangeWithRotation)),
+        ];
+        for (config, mode, current, expected) in matrix {
+            let current_bits = current.identifier() as u32;
+            let word = thermistor(config, mode, current).to_config_word();
+            //|21-19| sensor configuration, |18-15| excitation current
+            assert_eq!(word.map(|word| ((word >> 19) & 0x7, (word >> 15) & 0xf)), expected.map(|bits| (bits, current_bits)));
+        }
+
+        let word = thermistor(SensorConfiguration::Differential, ThermistorExcitationMode::SharingRotation, ThermistorExcitationCurrent::I10uA).to_config_word().unwrap();
+        let decoded = ThermalProbeType::from_config_word(word).unwrap();
+        assert_eq!(decoded.thermistor_parameters().unwrap().excitation_mode, ThermistorExcitationMode::SharingRotation);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH20).unwrap();
+        ltc.set_global_configuration(GlobalConfiguration::default().rejection(RejectionFilter::Reject50Hz)).unwrap();
+        ltc.set_mux_delay(1_000).unwrap();
+        ltc.start_multi_conversion(&[LTC2983Channel::CH20]).unwrap();
+        ltc.spi_device.writes.clear();
+
+        let snapshot = ltc.snapshot().unwrap();
+        assert_eq!(snapshot.global_configuration, 0x02);
+        assert_eq!(snapshot.multi_channel_mask, 0x0008_0000);
+        assert_eq!(snapshot.mux_delay, 10);
+        assert_eq!(snapshot.channel_config[19], 0xE81F4000);
+        assert!(snapshot.channel_config[..19].iter().all(|word| *word == 0));
+        assert!(ltc.spi_device.writes.is_empty());
+        assert!(serde_json::to_string(&snapshot).unwrap().contains("\"mux_delay\":10"));
+    }
+
+    #[test]
+    fn test_disable_channel() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH3).unwrap();
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default()), LTC2983Channel::CH5).unwrap();
+
+        ltc.disable_channel(LTC2983Channel::CH3).unwrap();
+        assert_eq!(ltc.spi_device.writes.last().unwrap(), &vec![LTC2983_WRITE, 0x02, 0x08, 0, 0, 0, 0]);
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH3), None);
+        assert_eq!(ltc.enabled_channels().unwrap(), vec![LTC2983Channel::CH5]);
+        assert!(matches!(ltc.start_multi_conversion(&[LTC2983Channel::CH3]), Err(LTC2983Error::ChannelUnconfigured(LTC2983Channel::CH3))));
+    }
+
+    #[test]
+    fn test_multi_conversion_of_channels_configured_on_the_device() {
+        //CH3 was set up before the driver was created, e.g. restored from the EEPROM
+        let mut chip = FakeChip::new();
+        let addr = LTC2983Channel::CH3.start_address() as usize;
+        chip.mem[addr..addr + 4].copy_from_slice(&ThermalProbeType::Diode(DiodeParameters::default()).to_config_word().unwrap().to_be_bytes());
+        let mut ltc = LTC2983::new(chip);
+
+        assert!(matches!(ltc.start_multi_conversion(&[LTC2983Channel::CH3, LTC2983Channel::CH5]), Err(LTC2983Error::ChannelUnconfigured(LTC2983Channel::CH5))));
+        assert!(ltc.spi_device.writes.is_empty());
+        ltc.start_multi_conversion(&[LTC2983Channel::CH3]).unwrap();
+        assert_eq!(ltc.spi_device.writes[0], vec![LTC2983_WRITE, 0x00, 0xf4, 0x00, 0x00, 0x00, 0x04]);
+    }
+
+    #[test]
+    fn test_multi_temperature_avg_progress() {
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x00], [0x81, 0x00, 0x00, 0x00], [0x01, 0x00, 0x68, 0x00]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x68, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+        let channels = [LTC2983Channel::CH1, LTC2983Channel::CH3];
+        assume_diodes(&mut ltc, &channels);
+
+        let mut reported = Vec::new();
+        let averages = ltc.get_multi_temperature_avg_with_progress(&channels, 2, |round, results| {
+            reported.push((round, results.iter().map(|result| matches!(result, LTC2983Result::Valid(_))).collect::<Vec<_>>()));
+        }).unwrap();
+        assert_eq!(averages, vec![25.5, 26.]);
+        assert_eq!(reported, vec![(0, vec![true, true]), (1, vec![false, true]), (1, vec![true, true])]);
+
+        //an always invalid channel gives up after every round got its retry
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH1, &[[0x01, 0x00, 0x64, 0x00]])
+            .result_sequence(LTC2983Channel::CH3, &[[0x81, 0x00, 0x00, 0x00]]);
+        let mut ltc = LTC2983::new(chip);
+        assume_diodes(&mut ltc, &channels);
+        let mut rounds_seen = 0;
+        let res = ltc.get_multi_temperature_avg_with_progress(&channels, 2, |_, _| rounds_seen += 1);
+        assert!(matches!(res, Err(LTC2983Error::SamplesRejected(3, 2))));
+        assert_eq!(rounds_seen, 3);
+
+        //a result that can not be read is an error
+        let mut ltc = LTC2983::new(FakeChip::new().failing_reads_of(LTC2983Channel::CH3.result_address()));
+        assume_diodes(&mut ltc, &channels);
+        assert!(matches!(ltc.get_multi_temperature_avg_with_progress(&channels, 2, |_, _| {}), Err(LTC2983Error::SpiError(_))));
+    }
+
+    #[test]
+    fn test_suspect_policy() {
+        let results = [
+            [0x11, 0x00, 0x64, 0x00], // 25.0 with CJ soft fault
+            [0x09, 0x00, 0x64, 0x00], // 25.0 over range
+            [0x81, 0x00, 0x00, 0x00],
+        ];
+        let read_all = |policy| {
+            let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH3, &results));
+            ltc.set_suspect_policy(policy);
+            (0..3).map(|_| ltc.measure(LTC2983Channel::CH3).unwrap().result).collect::<Vec<_>>()
+        };
+
+        let kept = read_all(SuspectPolicy::Keep);
+        assert!(matches!(kept[..], [LTC2983Result::Suspect(..), LTC2983Result::Suspect(..), LTC2983Result::Invalid(_)]));
+        let accepted = read_all(SuspectPolicy::Accept);
+        assert!(matches!(accepted[..], [LTC2983Result::Valid(_), LTC2983Result::Valid(_), LTC2983Result::Invalid(_)]));
+        let rejected = read_all(SuspectPolicy::Reject);
+        assert!(matches!(rejected[..], [LTC2983Result::Invalid(_), LTC2983Result::Invalid(f), LTC2983Result::Invalid(_)] if f == FaultFlags::SENSOR_OVER_RANGE));
+        let cj_only = read_all(SuspectPolicy::AcceptIfOnly(FaultFlags::CJ_SOFT_FAULT));
+        assert!(matches!(cj_only[..], [LTC2983Result::Valid(_), LTC2983Result::Invalid(_), LTC2983Result::Invalid(_)]));
+    }
+
+    #[test]
+    fn test_spectrum_thermistor() {
+        let probe = ThermalProbeType::Thermistor_Spectrum(ThermistorParameters::default());
+        // |31-27| type 25, |26-22| sense resistor CH2, |21-19| single-ended, |18-15| auto range
+        assert_eq!(probe.to_config_word().unwrap(), 0xC8A6_0000);
+        assert_eq!(probe.kind(), SensorKind::Thermistor_Spectrum);
+        assert!(matches!(ThermalProbeType::from_config_word(0xC8A6_0000), Some(ThermalProbeType::Thermistor_Spectrum(_))));
+    }
+
+    #[test]
+    fn test_rtd_excitation_for_type() {
+        let current = |kind| RTDParameters::for_type(kind).excitation_current.microamps();
+        assert_eq!(current(SensorKind::RTD_PT10), 1000.);
+        assert_eq!(current(SensorKind::RTD_PT100), 250.);
+        assert_eq!(current(SensorKind::RTD_PT1000), 50.);
+        assert_eq!(current(SensorKind::Diode), 5.);
+
+        let kinds = [SensorKind::RTD_PT10, SensorKind::RTD_PT50, SensorKind::RTD_PT100, SensorKind::RTD_PT200, SensorKind::RTD_PT500, SensorKind::RTD_PT1000];
+        let ohms = [10., 50., 100., 200., 500., 1000.];
+        for (kind, ohms) in kinds.into_iter().zip(ohms) {
+            let microwatts = current(kind).powi(2) * ohms / 1e6;
+            assert!(microwatts <= 12.5, "{kind:?} dissipates {microwatts}µW");
+        }
+    }
+
+    #[test]
+    fn test_rtd_self_heating() {
+        let pt100 = RTDParameters::default().excitation_current(RTDExcitationCurrent::I1mA);
+        assert!((pt100.estimated_self_heating_mw(100.) - 0.1).abs() < 1e-6);
+        assert_eq!(RTDParameters::default().excitation_current(RTDExcitationCurrent::External).estimated_self_heating_mw(100.), 0.);
+    }
+
+    #[test]
+    fn test_read_latest() {
+        let chip = FakeChip::new()
+            .result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x00], [0x01, 0x00, 0x68, 0x00]])
+            .result_sequence(LTC2983Channel::CH5, &[[0x01, 0x00, 0x6C, 0x00]])
+            .busy_polls(2);
+        let mut ltc = LTC2983::with_waiter(chip, PollWaiter::new(5));
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH3, LTC2983Channel::CH5]);
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH3), Err(LTC2983Error::StaleResult(LTC2983Channel::CH3))));
+
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH3), Err(LTC2983Error::StaleResult(_))));
+        ltc.wait_for_conversion().unwrap();
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH3), Ok(LTC2983Result::Valid(Reading::Temperature(Celsius(t)))) if t == 25.0));
+        //the same conversion is not returned twice
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH3), Err(LTC2983Error::StaleResult(_))));
+
+        //the result of CH3 is not read, but the last command converted another channel
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        ltc.wait_for_conversion().unwrap();
+        ltc.start_conversion(LTC2983Channel::CH5).unwrap();
+        ltc.wait_for_conversion().unwrap();
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH3), Err(LTC2983Error::StaleResult(_))));
+        assert!(ltc.read_latest(LTC2983Channel::CH5).is_ok());
+
+        ltc.start_multi_conversion(&[LTC2983Channel::CH3, LTC2983Channel::CH5]).unwrap();
+        ltc.wait_for_conversion().unwrap();
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH3), Ok(LTC2983Result::Valid(Reading::Temperature(Celsius(t)))) if t == 25.0));
+        assert!(matches!(ltc.read_latest(LTC2983Channel::CH5), Ok(LTC2983Result::Valid(Reading::Temperature(Celsius(t)))) if t == 27.0));
+    }
+
+    #[test]
+    fn test_measure_within() {
+        let mut delay = FakeDelay { waited_ns: 0 };
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(10));
+        assert!(matches!(ltc.measure_within(LTC2983Channel::CH3, &mut &mut delay, None), Err(LTC2983Error::ChannelUnconfigured(LTC2983Channel::CH3))));
+        assert!(ltc.spi_device.writes.is_empty());
+
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH3]);
+        assert!(matches!(ltc.measure_within(LTC2983Channel::CH3, &mut &mut delay, Some(Duration::from_millis(3))), Err(LTC2983Error::ConversionTimeout)));
+        assert_eq!(delay.waited_ns, 3_000_000);
+
+        //the estimate of a diode with the default filter is far above the 10 busy polls
+        let mut delay = FakeDelay { waited_ns: 0 };
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(10));
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH3]);
+        assert_eq!(ltc.measure_within(LTC2983Channel::CH3, &mut &mut delay, None).unwrap().channel, LTC2983Channel::CH3);
+        assert!(delay.waited_ns > 0);
+
+        //a sense resistor estimates no conversion time, it still gets a conversion cycle
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(10));
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        assert_eq!(ltc.measure_within(LTC2983Channel::CH2, &mut &mut delay, None).unwrap().channel, LTC2983Channel::CH2);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let valid = ChannelMap::new()
+            .channel(LTC2983Channel::CH2, ThermalProbeType::SenseResistor(2000.))
+            .channel(LTC2983Channel::CH4, ThermalProbeType::RTD_PT100(RTDParameters::default().channel(LTC2983Channel::CH2)))
+            .channel(LTC2983Channel::CH6, ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().cold_junction(LTC2983Channel::CH4)));
+        assert_eq!(validate_config(&valid), Ok(()));
+
+        let invalid = ChannelMap::new()
+            .channel(LTC2983Channel::CH1, ThermalProbeType::SenseResistor(2000.))
+            .channel(LTC2983Channel::CH4, ThermalProbeType::RTD_PT100(RTDParameters::default().channel(LTC2983Channel::CH2)))
+            .channel(LTC2983Channel::CH6, ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default()
+                .cold_junction(LTC2983Channel::CH7)
+                .custom_table(0x100, 4)));
+        assert_eq!(validate_config(&invalid), Err(vec![
+            (LTC2983Channel::CH1, ConfigError::SenseResistorOnCh1),
+            (LTC2983Channel::CH4, ConfigError::SenseResistorUnconfigured(LTC2983Channel::CH2)),
+            (LTC2983Channel::CH6, ConfigError::CustomAddressOutOfRange(0x100)),
+            (LTC2983Channel::CH6, ConfigError::ColdJunctionUnconfigured(LTC2983Channel::CH7)),
+        ]));
+
+        let differential = ChannelMap::new()
+            .channel(LTC2983Channel::CH1, ThermalProbeType::DirectADC(DirectADCParameters::default().sensor_configuration(SensorConfiguration::Differential)));
+        assert_eq!(validate_config(&differential), Err(vec![(LTC2983Channel::CH1, ConfigError::DifferentialOnCh1)]));
+    }
+
+    #[test]
+    fn test_reset_during_conversion() {
+        let chip = FakeChip::new().result_sequence(LTC2983Channel::CH5, &[[0x01, 0x00, 0x64, 0x00]]).busy_polls(3);
+        let mut ltc = LTC2983::with_waiter(chip, PollWaiter::new(5));
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH3, LTC2983Channel::CH5]);
+        ltc.start_multi_conversion(&[LTC2983Channel::CH3, LTC2983Channel::CH5]).unwrap();
+        ltc.reset().unwrap();
+        let status = ltc.status().unwrap();
+        assert!(status.done() && !status.started());
+        assert_eq!(ltc.read_register(LTC2983Channel::CH5.result_address(), 4).unwrap(), [0; 4]);
+        assert!(ltc.read_multi_channel_mask().unwrap().is_empty());
+
+        //a conversion that never finishes is left alone
+        let mut ltc = LTC2983::with_waiter(FakeChip::new().stuck_channel(LTC2983Channel::CH3), PollWaiter::new(5));
+        assume_diodes(&mut ltc, &[LTC2983Channel::CH3]);
+        ltc.start_conversion(LTC2983Channel::CH3).unwrap();
+        let writes = ltc.spi_device.writes.len();
+        assert!(matches!(ltc.reset(), Err(LTC2983Error::ConversionTimeout)));
+        assert_eq!(ltc.spi_device.writes.len(), writes);
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH3), Some(SensorKind::Diode));
+    }
+
+    #[test]
+    fn test_sensor_kind_type_code() {
+        let kinds: Vec<_> = (0..32).filter_map(SensorKind::from_type_code).collect();
+        assert_eq!(kinds.len(), 27);
+        assert!(kinds.iter().all(|kind| SensorKind::from_type_code(kind.type_code()) == Some(*kind)));
+        assert_eq!(SensorKind::from_type_code(0), None);
+        assert_eq!(SensorKind::from_type_code(18), None);
+
+        let probe = ThermalProbeType::RTD_PT100(RTDParameters::default());
+        let kind = SensorKind::from_type_code(probe.identifier()).unwrap();
+        assert_eq!(kind, probe.kind());
+        assert_eq!(kind.name(), "RTD PT-100");
+        assert_eq!(u64::from(kind), 12);
+    }
+
+    #[test]
+    fn test_read_channel_kinds() {
+        assert_eq!(SensorKind::from_config_word(0xE81F4000), Some(SensorKind::SenseResistor));
+        assert_eq!(SensorKind::from_config_word(0), None);
+
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.setup_channel(ThermalProbeType::RTD_PT100(RTDParameters::default()), LTC2983Channel::CH4).unwrap();
+        //restored by the device, unknown to the driver
+        ltc.write_register(LTC2983Channel::CH9.start_address(), &0xE7C00000u32.to_be_bytes()).unwrap();
+        assert_eq!(ltc.read_channel_kinds().unwrap(), [
+            (LTC2983Channel::CH2, SensorKind::SenseResistor),
+            (LTC2983Channel::CH4, SensorKind::RTD_PT100),
+            (LTC2983Channel::CH9, SensorKind::Diode),
+        ]);
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH9), None);
+    }
+
+    #[test]
+    fn test_result_temperature() {
+        let valid = LTC2983Result::Valid(Reading::Temperature(Celsius(25.)));
+        let suspect = LTC2983Result::Suspect(Reading::Temperature(Celsius(26.)), FaultFlags::CJ_SOFT_FAULT);
+        let invalid = LTC2983Result::Invalid(FaultFlags::SENSOR_HARD_FAULT);
+        assert_eq!(valid.temperature(), Some(25.));
+        assert_eq!(suspect.temperature(), Some(26.));
+        assert_eq!(invalid.temperature(), None);
+        assert_eq!(LTC2983Result::Valid(Reading::Voltage(Volts(0.5))).temperature(), None);
+
+        assert_eq!(valid.into_result(), Ok(25.));
+        assert_eq!(suspect.into_result(), Err(FaultFlags::CJ_SOFT_FAULT));
+        assert_eq!(invalid.into_result(), Err(FaultFlags::SENSOR_HARD_FAULT));
+        assert_eq!(LTC2983Result::Valid(Reading::Resistance(Ohms(2000.))).into_result(), Ok(2000.));
+    }
+
+    #[test]
+    fn test_differential_on_ch1() {
+        let differential = || ThermalProbeType::Thermocouple_K(ThermocoupleParameters::default().sensor_configuration(SensorConfiguration::Differential));
+        let mut ltc = LTC2983::new(FakeChip::new());
+        assert!(matches!(ltc.setup_channel(differential(), LTC2983Channel::CH1), Err(LTC2983Error::InvalidConfig(ConfigError::DifferentialOnCh1))));
+        let diode = ThermalProbeType::Diode(DiodeParameters::default().sensor_configuration(SensorConfiguration::Differential));
+        assert!(matches!(ltc.setup_channel(diode, LTC2983Channel::CH1), Err(LTC2983Error::InvalidConfig(ConfigError::DifferentialOnCh1))));
+        let builder = ChannelConfigBuilder::new().channel(LTC2983Channel::CH1, ThermalProbeType::SenseResistor(2000.));
+        assert!(matches!(builder.commit(&mut ltc), Err(LTC2983Error::InvalidConfig(ConfigError::SenseResistorOnCh1))));
+        assert!(ltc.spi_device.writes.is_empty());
+
+        ltc.setup_channel(differential(), LTC2983Channel::CH2).unwrap();
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH2), Some(SensorKind::Thermocouple_K));
+    }
+
+    #[test]
+    fn test_write_verify() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.set_write_verify(true);
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+
+        //every transaction flips the lowest bit of the CH4 configuration register
+        struct Corrupting(FakeChip);
+        impl embedded_hal::spi::ErrorType for Corrupting {
+            type Error = FakeSpiError;
+        }
+        impl SpiDevice for Corrupting {
+            fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), FakeSpiError> {
+                self.0.transaction(operations)?;
+                let word = LTC2983Channel::CH4.start_address() as usize;
+                self.0.mem[word + 3] ^= 0x01;
+                Ok(())
+            }
+        }
+        let mut ltc = LTC2983::new(Corrupting(FakeChip::new()));
+        ltc.set_write_verify(true);
+        let word = ThermalProbeType::SenseResistor(2000.).to_config_word().unwrap();
+        assert!(matches!(ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH4),
+                         Err(LTC2983Error::WriteVerifyFailed { channel: LTC2983Channel::CH4, expected, actual }) if expected == word && actual == word ^ 0x01));
+        assert_eq!(ltc.configured_kind(LTC2983Channel::CH4), None);
+    }
+
+    #[test]
+    fn test_read_global_settings() {
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.set_global_configuration(GlobalConfiguration::default().fahrenheit(true).rejection(RejectionFilter::Reject60Hz)).unwrap();
+        ltc.set_mux_delay(250).unwrap();
+        assert_eq!(ltc.read_global_settings().unwrap(), GlobalSettings {
+            temperature_unit: TemperatureUnit::Fahrenheit,
+            rejection_filter: RejectionFilter::Reject60Hz,
+            mux_delay_us: 300
+        });
+    }
+
+    #[test]
+    fn test_only_hard_faults_invalidate_result() {
+        // bits 31-29 of the result word are the hard faults, the lower fault bits only make the reading suspect
+        for error_code in [0x80, 0x40, 0x20, 0xe1] {
+            assert!(matches!(LTC2983Result::from([error_code, 0x00, 0x64, 0x00]), LTC2983Result::Invalid(_)), "{error_code:#04x}");
+        }
+        for error_code in [0x11, 0x09, 0x05, 0x03] {
+            assert!(matches!(LTC2983Result::from([error_code, 0x00, 0x64, 0x00]), LTC2983Result::Suspect(..)), "{error_code:#04x}");
+        }
+    }
+
+    #[test]
+    fn test_fixed_average_matches_float_average() {
+        let samples: [[u8; 4]; 4] = [
+            [0x01, 0x00, 0x64, 0x66], // 25.1
+            [0x01, 0x00, 0x64, 0xcc], // 25.2
+            [0x01, 0x00, 0x65, 0x33], // 25.3
+            [0x01, 0xfb, 0xbb, 0x67], // -273.15
+        ];
+        let fixed: Vec<FixedI32<U10>> = samples.iter().map(fixed_from_result_bytes).collect();
+        let float_avg = samples.iter().map(|s| match LTC2983Result::from(*s) {
+            LTC2983Result::Valid(Reading::Temperature(Celsius(v))) => v,
+            r => panic!("unexpected result {r:?}"),
+        }).sum::<f32>() / samples.len() as f32;
+
+        let fixed_avg = average_fixed(&fixed).unwrap();
+        assert!((fixed_avg.to_num::<f32>() - float_avg).abs() < 1./1024.);
+        assert_eq!(average_fixed(&[]), None);
+    }
+
+    #[test]
+    fn test_temperature_avg_fixed_decodes_like_reads() {
+        //77 °F reported by a device configured for °F is 25 °C
+        let mut ltc = LTC2983::new(FakeChip::new().busy_polls(1).result_sequence(LTC2983Channel::CH3, &[[0x01, 0x01, 0x34, 0x00]]));
+        ltc.set_global_configuration(GlobalConfiguration::default().fahrenheit(true)).unwrap();
+        assert_eq!(ltc.get_temperature_avg_fixed(LTC2983Channel::CH3, 2).unwrap(), FixedI32::<U10>::from_num(25));
+        assert_eq!(ltc.conversion_state(), ConversionState::Idle);
+        assert_eq!(ltc.unread & LTC2983Channel::CH3.mask(), 0);
+
+        //the diode offset is applied and accepted suspects count
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x00], [0x11, 0x00, 0x68, 0x00]]));
+        ltc.setup_channel(ThermalProbeType::Diode(DiodeParameters::default().offset_c(-1.5)), LTC2983Channel::CH3).unwrap();
+        assert!(matches!(ltc.get_temperature_avg_fixed(LTC2983Channel::CH3, 2), Err(LTC2983Error::SamplesRejected(1, 2))));
+        ltc.set_suspect_policy(SuspectPolicy::Accept);
+        assert_eq!(ltc.get_temperature_avg_fixed(LTC2983Channel::CH3, 2).unwrap(), FixedI32::<U10>::from_num(24));
+
+        let mut ltc = LTC2983::new(FakeChip::new().result_sequence(LTC2983Channel::CH3, &[[0x01, 0x00, 0x64, 0x00]]));
+        ltc.set_linearization(LTC2983Channel::CH3, |t| t + 1.);
+        assert_eq!(ltc.get_temperature_avg_fixed(LTC2983Channel::CH3, 2).unwrap(), FixedI32::<U10>::from_num(26));
+
+        //a 2000 Ω sense resistor has no temperature
+        let mut ltc = LTC2983::new(FakeChip::new());
+        ltc.setup_channel(ThermalProbeType::SenseResistor(2000.), LTC2983Channel::CH2).unwrap();
+        ltc.spi_device.writes.clear();
+        assert!(matches!(ltc.get_temperature_avg_fixed(LTC2983Channel::CH2, 2), Err(LTC2983Error::NotATemperature(LTC2983Channel::CH2))));
+        assert!(ltc.spi_device.writes.is_empty());
     }
 }
@@ -0,0 +1,39 @@
+//! Encoding of the device configuration without any SPI device.
+//!
+//! The functions in this module compute the register writes the driver performs, as pairs of the
+//! start address and the bytes written there. This can be used to pre-generate a configuration
+//! blob for a whole board, or to compare the configuration against a logic analyzer capture.
+//! A write on the bus consists of the write instruction (0x02), the big-endian address and the bytes.
+
+use crate::{mux_delay_bits, ConfigError, GlobalConfiguration, LTC2983Channel, ThermalProbeType, GLOBAL_CONFIG_REGISTER, MUX_CONFIG_DELAY_REGISTER};
+
+///the writes of the channel configuration registers for `channels`
+///
+///The registers are contiguous from 0x200, neighbouring channels are combined into a single write.
+///The writes are ordered by channel, a channel assigned twice is written twice in the given order.
+///Fails if any of the sensors can not be encoded, see [`ThermalProbeType::to_config_word`].
+pub fn channel_config_writes(channels: &[(LTC2983Channel, ThermalProbeType)]) -> Result<Vec<(u16, Vec<u8>)>, ConfigError> {
+    let mut channels: Vec<_> = channels.iter().collect();
+    channels.sort_by_key(|(channel, _)| channel.index());
+
+    channels
+        .chunk_by(|a, b| a.0.index() + 1 == b.0.index())
+        .map(|burst| {
+            let mut bytes = Vec::with_capacity(4 * burst.len());
+            for (_, probe) in burst {
+                bytes.extend_from_slice(&probe.to_config_word()?.to_be_bytes());
+            }
+            Ok((burst[0].0.start_address(), bytes))
+        })
+        .collect()
+}
+
+///the write of the global configuration register
+pub fn global_config_write(config: &GlobalConfiguration) -> (u16, Vec<u8>) {
+    (GLOBAL_CONFIG_REGISTER, vec![config.to_bits()])
+}
+
+///the write of the mux delay register, see [`crate::LTC2983::set_mux_delay`]
+pub fn mux_delay_write(delay_us: u32) -> (u16, Vec<u8>) {
+    (MUX_CONFIG_DELAY_REGISTER, vec![mux_delay_bits(delay_us)])
+}